@@ -0,0 +1,87 @@
+use crate::Error;
+use crate::Result;
+use phf::phf_map;
+use std::collections::HashMap;
+use std::collections::HashSet;
+
+// Source: https://web.archive.org/web/20200205183157/http://www.data-compression.com/english.html
+pub static LETTER_FREQUENCY: phf::Map<char, f64> = phf_map! {
+    'a' => 0.0651738,
+    'b' => 0.0124248,
+    'c' => 0.0217339,
+    'd' => 0.0349835,
+    'e' => 0.1041442,
+    'f' => 0.0197881,
+    'g' => 0.0158610,
+    'h' => 0.0492888,
+    'i' => 0.0558094,
+    'j' => 0.0009033,
+    'k' => 0.0050529,
+    'l' => 0.0331490,
+    'm' => 0.0202124,
+    'n' => 0.0564513,
+    'o' => 0.0596302,
+    'p' => 0.0137645,
+    'q' => 0.0008606,
+    'r' => 0.0497563,
+    's' => 0.0515760,
+    't' => 0.0729357,
+    'u' => 0.0225134,
+    'v' => 0.0082903,
+    'w' => 0.0171272,
+    'x' => 0.0013692,
+    'y' => 0.0145984,
+    'z' => 0.0007836,
+    ' ' => 0.1918182,
+};
+
+pub fn hamming_distance(x: &[u8], y: &[u8]) -> Result<f64> {
+    if x.len() != y.len() {
+        return Err(Error::LengthMismatch {
+            expected: x.len(),
+            actual: y.len(),
+        });
+    }
+
+    Ok(x.iter()
+        .zip(y)
+        .fold(0.0, |a, (b, c)| a + (*b ^ *c).count_ones() as f64))
+}
+
+pub(crate) fn score_plaintext(char_count: &mut HashMap<char, f64>) -> f64 {
+    let mut score = 0f64;
+
+    for (character, frequency) in LETTER_FREQUENCY.entries() {
+        match char_count.get(character) {
+            Some(count) => score += (*frequency * count).sqrt(),
+            None => continue,
+        }
+    }
+
+    score
+}
+
+pub fn detect_aes_ecb_mode(blocks: Vec<Vec<u8>>) -> Option<String> {
+    for data in blocks {
+        if data.len() % 16 == 0 {
+            let mut set: HashSet<&[u8]> = HashSet::new();
+            let mut duplicate_blocks = 0;
+            for block in data.chunks(16) {
+                match set.get(block) {
+                    Some(_) => {
+                        duplicate_blocks += 1;
+                    }
+                    None => {
+                        set.insert(block);
+                    }
+                }
+            }
+
+            if duplicate_blocks > 0 {
+                return Some(hex::encode(data));
+            }
+        }
+    }
+
+    None
+}
@@ -0,0 +1,193 @@
+use crate::cipher::Aes;
+use crate::modes::aes_128_cbc_crypter;
+use crate::modes::CipherMode;
+use crate::modes::Ctr;
+use crate::modes::Mode;
+use crate::utils::random_bytes;
+use crate::Error;
+use crate::Result;
+
+const USERDATA_PREFIX: &[u8] = b"comment1=cooking%20MCs;userdata=";
+const USERDATA_SUFFIX: &[u8] = b";comment2=%20like%20a%20pound%20of%20bacon";
+
+pub fn wrap_userdata(input: &[u8]) -> Vec<u8> {
+    let mut out = USERDATA_PREFIX.to_vec();
+
+    for byte in input {
+        match byte {
+            b'%' => out.extend_from_slice(b"%25"),
+            b';' => out.extend_from_slice(b"%3B"),
+            b'=' => out.extend_from_slice(b"%3D"),
+            byte => out.push(*byte),
+        }
+    }
+
+    out.extend_from_slice(USERDATA_SUFFIX);
+    out
+}
+
+pub fn has_admin_role(plaintext: &[u8]) -> bool {
+    plaintext
+        .windows(b";admin=true;".len())
+        .any(|window| window == b";admin=true;")
+}
+
+pub struct CbcUserDataService {
+    key: Vec<u8>,
+    iv: Vec<u8>,
+}
+
+impl CbcUserDataService {
+    pub fn new() -> CbcUserDataService {
+        CbcUserDataService {
+            key: random_bytes(16),
+            iv: random_bytes(16),
+        }
+    }
+
+    pub fn encrypt(&self, userdata: &[u8]) -> Result<Vec<u8>> {
+        aes_128_cbc_crypter(&self.key, &self.iv, &wrap_userdata(userdata), Mode::Encrypt)
+    }
+
+    pub fn is_admin(&self, ciphertext: &[u8]) -> Result<bool> {
+        let plaintext = aes_128_cbc_crypter(&self.key, &self.iv, ciphertext, Mode::Decrypt)?;
+        Ok(has_admin_role(&plaintext))
+    }
+}
+
+impl Default for CbcUserDataService {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// Flipping a bit in ciphertext block `block_index` flips the same bit of the next plaintext
+// block (and scrambles this one), so XORing in known ^ desired rewrites the start of that block.
+pub fn cbc_bitflip(
+    ciphertext: &mut [u8],
+    block_size: usize,
+    block_index: usize,
+    known: &[u8],
+    desired: &[u8],
+) -> Result<()> {
+    if known.len() != desired.len() {
+        return Err(Error::LengthMismatch {
+            expected: known.len(),
+            actual: desired.len(),
+        });
+    }
+
+    if block_size == 0 {
+        return Err(Error::InvalidBlockSize(block_size));
+    }
+
+    // Only the one plaintext block after the flipped ciphertext block can be rewritten.
+    if known.len() > block_size {
+        return Err(Error::LengthMismatch {
+            expected: block_size,
+            actual: known.len(),
+        });
+    }
+
+    let start = block_index * block_size;
+    let block = ciphertext
+        .get_mut(start..start + known.len())
+        .ok_or(Error::AttackFailed("block index out of range"))?;
+
+    for ((byte, k), d) in block.iter_mut().zip(known).zip(desired) {
+        *byte ^= k ^ d;
+    }
+
+    Ok(())
+}
+
+// The userdata is padded out to a block boundary and followed by two blocks of 'A': the first
+// is sacrificed and the second is rewritten into ";admin=true;".
+pub fn forge_cbc_admin(service: &CbcUserDataService) -> Result<Vec<u8>> {
+    let desired = b";admin=true;";
+    let known = vec![b'A'; desired.len()];
+
+    let sacrificed = USERDATA_PREFIX.len().div_ceil(Aes::BLOCK_SIZE);
+    let align = sacrificed * Aes::BLOCK_SIZE - USERDATA_PREFIX.len();
+
+    let mut ciphertext = service.encrypt(&vec![b'A'; align + 2 * Aes::BLOCK_SIZE])?;
+    cbc_bitflip(
+        &mut ciphertext,
+        Aes::BLOCK_SIZE,
+        sacrificed,
+        &known,
+        desired,
+    )?;
+
+    Ok(ciphertext)
+}
+
+pub struct CtrUserDataService {
+    ctr: Ctr<Aes>,
+}
+
+impl CtrUserDataService {
+    pub fn new() -> Result<CtrUserDataService> {
+        Ok(CtrUserDataService {
+            ctr: Ctr::new(Aes::new(&random_bytes(16))?, rand::random())?,
+        })
+    }
+
+    pub fn encrypt(&self, userdata: &[u8]) -> Result<Vec<u8>> {
+        self.ctr.encrypt(&wrap_userdata(userdata))
+    }
+
+    pub fn is_admin(&self, ciphertext: &[u8]) -> Result<bool> {
+        Ok(has_admin_role(&self.ctr.decrypt(ciphertext)?))
+    }
+}
+
+// In CTR every ciphertext bit maps straight onto the same plaintext bit, so any byte can be
+// rewritten in place without disturbing its neighbours.
+pub fn ctr_bitflip(
+    ciphertext: &mut [u8],
+    offset: usize,
+    known: &[u8],
+    desired: &[u8],
+) -> Result<()> {
+    if known.len() != desired.len() {
+        return Err(Error::LengthMismatch {
+            expected: known.len(),
+            actual: desired.len(),
+        });
+    }
+
+    let segment = ciphertext
+        .get_mut(offset..offset + known.len())
+        .ok_or(Error::AttackFailed("offset out of range"))?;
+
+    for ((byte, k), d) in segment.iter_mut().zip(known).zip(desired) {
+        *byte ^= k ^ d;
+    }
+
+    Ok(())
+}
+
+pub fn forge_ctr_admin(service: &CtrUserDataService) -> Result<Vec<u8>> {
+    let desired = b";admin=true;";
+    let known = vec![b'A'; desired.len()];
+
+    let mut ciphertext = service.encrypt(&known)?;
+    ctr_bitflip(&mut ciphertext, USERDATA_PREFIX.len(), &known, desired)?;
+
+    Ok(ciphertext)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Error;
+
+    #[test]
+    fn cbc_bitflip_rejects_overlong_known_text() {
+        let mut ciphertext = vec![0u8; 16];
+        assert!(matches!(
+            super::cbc_bitflip(&mut ciphertext, 8, 0, &[0; 9], &[1; 9]),
+            Err(Error::LengthMismatch { .. })
+        ));
+    }
+}
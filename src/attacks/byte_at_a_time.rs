@@ -0,0 +1,211 @@
+use crate::cipher::Aes;
+use crate::modes::CipherMode;
+use crate::modes::Ecb;
+use crate::utils::random_bytes;
+use crate::Error;
+use crate::Result;
+use rand::Rng;
+
+pub trait EcbOracle {
+    fn encrypt(&self, input: &[u8]) -> Result<Vec<u8>>;
+}
+
+// AES-128-ECB(prefix || attacker input || secret, random key)
+pub struct EcbOracleServer {
+    ecb: Ecb<Aes>,
+    prefix: Vec<u8>,
+    secret: Vec<u8>,
+}
+
+impl EcbOracleServer {
+    pub fn new(secret: &[u8]) -> Result<EcbOracleServer> {
+        Self::with_prefix(&[], secret)
+    }
+
+    pub fn with_random_prefix(secret: &[u8]) -> Result<EcbOracleServer> {
+        let prefix_len = rand::thread_rng().gen_range(0..=64);
+        Self::with_prefix(&random_bytes(prefix_len), secret)
+    }
+
+    pub fn with_prefix(prefix: &[u8], secret: &[u8]) -> Result<EcbOracleServer> {
+        Ok(EcbOracleServer {
+            ecb: Ecb::new(Aes::new(&random_bytes(16))?),
+            prefix: prefix.to_vec(),
+            secret: secret.to_vec(),
+        })
+    }
+}
+
+impl EcbOracle for EcbOracleServer {
+    fn encrypt(&self, input: &[u8]) -> Result<Vec<u8>> {
+        let mut plaintext = self.prefix.clone();
+        plaintext.extend_from_slice(input);
+        plaintext.extend_from_slice(&self.secret);
+
+        self.ecb.encrypt(&plaintext)
+    }
+}
+
+// Returns the block size together with the number of input bytes that first caused the
+// ciphertext to grow by a block.
+fn discover_block_size<O: EcbOracle>(oracle: &O) -> Result<(usize, usize)> {
+    let base_len = oracle.encrypt(&[])?.len();
+
+    for input_len in 1..=256 {
+        let len = oracle.encrypt(&vec![b'A'; input_len])?.len();
+        if len > base_len {
+            return Ok((len - base_len, input_len));
+        }
+    }
+
+    Err(Error::AttackFailed("ciphertext length never changed"))
+}
+
+pub fn detect_block_size<O: EcbOracle>(oracle: &O) -> Result<usize> {
+    Ok(discover_block_size(oracle)?.0)
+}
+
+// Searches from block `start` onwards, returning the index of the first of two equal blocks.
+fn first_repeated_block(ciphertext: &[u8], block_size: usize, start: usize) -> Option<usize> {
+    let blocks = ciphertext
+        .chunks(block_size)
+        .skip(start)
+        .collect::<Vec<_>>();
+    blocks
+        .windows(2)
+        .position(|pair| pair[0] == pair[1])
+        .map(|index| start + index)
+}
+
+// The first block that differs between two single-byte inputs is the one attacker input lands
+// in. Anything before it is pure prefix, which may well repeat on its own.
+fn first_input_block<O: EcbOracle>(oracle: &O, block_size: usize) -> Result<usize> {
+    let first = oracle.encrypt(&[0])?;
+    let second = oracle.encrypt(&[1])?;
+
+    first
+        .chunks(block_size)
+        .zip(second.chunks(block_size))
+        .position(|(a, b)| a != b)
+        .ok_or(Error::AttackFailed(
+            "attacker input never reached the ciphertext",
+        ))
+}
+
+pub fn is_ecb<O: EcbOracle>(oracle: &O, block_size: usize) -> Result<bool> {
+    let ciphertext = oracle.encrypt(&vec![0u8; 3 * block_size])?;
+    Ok(first_repeated_block(&ciphertext, block_size, 0).is_some())
+}
+
+// Lines up two identical blocks of attacker input behind a varying amount of filler; the first
+// filler length that makes them encrypt identically reveals where the prefix ends. The prefix
+// could itself end in the fill byte, which only ever makes the estimate too small, so the
+// search runs with two different fill bytes and keeps the larger answer. Repeats inside the
+// prefix are ignored by only looking from the block attacker input starts in.
+pub fn detect_prefix_length<O: EcbOracle>(oracle: &O, block_size: usize) -> Result<usize> {
+    let start = first_input_block(oracle, block_size)?;
+    let mut prefix_len = None;
+
+    for &(filler, fill) in [(0x01u8, 0x00u8), (0xfe, 0xff)].iter() {
+        let mut found = None;
+
+        for pad in 0..block_size {
+            let mut input = vec![filler; pad];
+            input.extend(vec![fill; 2 * block_size]);
+
+            let ciphertext = oracle.encrypt(&input)?;
+            if let Some(index) = first_repeated_block(&ciphertext, block_size, start) {
+                found = Some(index * block_size - pad);
+                break;
+            }
+        }
+
+        let found = found.ok_or(Error::AttackFailed("could not align attacker input"))?;
+        prefix_len = Some(prefix_len.map_or(found, |len: usize| len.max(found)));
+    }
+
+    prefix_len.ok_or(Error::AttackFailed("could not align attacker input"))
+}
+
+pub fn byte_at_a_time_ecb_decryption<O: EcbOracle>(oracle: &O) -> Result<Vec<u8>> {
+    let (block_size, growth_input_len) = discover_block_size(oracle)?;
+
+    if !is_ecb(oracle, block_size)? {
+        return Err(Error::AttackFailed("oracle is not using ECB"));
+    }
+
+    let prefix_len = detect_prefix_length(oracle, block_size)?;
+    let base_len = oracle.encrypt(&[])?.len();
+    let secret_len = base_len - growth_input_len - prefix_len;
+
+    // Filler that pads the prefix out to a block boundary, and the first block we control.
+    let align = (block_size - prefix_len % block_size) % block_size;
+    let skip = prefix_len + align;
+
+    let mut recovered: Vec<u8> = Vec::with_capacity(secret_len);
+
+    while recovered.len() < secret_len {
+        let offset = recovered.len() % block_size;
+        let target_block = skip + (recovered.len() - offset);
+
+        let filler = vec![b'A'; align + block_size - 1 - offset];
+        let ciphertext = oracle.encrypt(&filler)?;
+        let target = &ciphertext[target_block..target_block + block_size];
+
+        let mut known = vec![b'A'; block_size - 1];
+        known.extend_from_slice(&recovered);
+        let known = &known[known.len() - (block_size - 1)..];
+
+        let mut probe = vec![b'A'; align];
+        probe.extend_from_slice(known);
+        probe.push(0);
+
+        let mut found = None;
+        for byte in 0..=255u8 {
+            probe[align + block_size - 1] = byte;
+            let ciphertext = oracle.encrypt(&probe)?;
+
+            if &ciphertext[skip..skip + block_size] == target {
+                found = Some(byte);
+                break;
+            }
+        }
+
+        match found {
+            Some(byte) => recovered.push(byte),
+            None => return Err(Error::AttackFailed("no byte matched the target block")),
+        }
+    }
+
+    Ok(recovered)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::EcbOracleServer;
+
+    #[test]
+    fn prefix_ending_in_fill_byte() {
+        let secret = b"attack at dawn, bring snacks";
+
+        for prefix in [
+            vec![0x00u8; 5],
+            vec![0xffu8; 5],
+            vec![b'Q'; 32],
+            vec![b'Q'; 37],
+        ]
+        .iter()
+        {
+            let server = EcbOracleServer::with_prefix(prefix, secret).unwrap();
+
+            assert_eq!(
+                super::detect_prefix_length(&server, 16).unwrap(),
+                prefix.len()
+            );
+            assert_eq!(
+                super::byte_at_a_time_ecb_decryption(&server).unwrap(),
+                secret
+            );
+        }
+    }
+}
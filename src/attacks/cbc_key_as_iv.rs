@@ -0,0 +1,77 @@
+use crate::cipher::Aes;
+use crate::modes::aes_128_cbc_key_as_iv_crypter;
+use crate::modes::Mode;
+use crate::utils::random_bytes;
+use crate::Error;
+use crate::Result;
+
+pub trait CbcReceiver {
+    fn receive(&self, ciphertext: &[u8]) -> Result<()>;
+}
+
+pub struct KeyAsIvService {
+    key: Vec<u8>,
+}
+
+impl KeyAsIvService {
+    pub fn new() -> KeyAsIvService {
+        KeyAsIvService {
+            key: random_bytes(16),
+        }
+    }
+
+    pub fn encrypt(&self, plaintext: &[u8]) -> Result<Vec<u8>> {
+        aes_128_cbc_key_as_iv_crypter(&self.key, plaintext, Mode::Encrypt)
+    }
+
+    pub fn is_key(&self, key: &[u8]) -> bool {
+        self.key == key
+    }
+}
+
+impl Default for KeyAsIvService {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl CbcReceiver for KeyAsIvService {
+    // Complains about high-ASCII plaintext, helpfully quoting it back.
+    fn receive(&self, ciphertext: &[u8]) -> Result<()> {
+        let plaintext = aes_128_cbc_key_as_iv_crypter(&self.key, ciphertext, Mode::Decrypt)?;
+
+        if plaintext.iter().any(|byte| *byte >= 0x80) {
+            return Err(Error::NonAsciiPlaintext(plaintext));
+        }
+
+        Ok(())
+    }
+}
+
+// Sending C1 || 0 || C1 makes the first block decrypt to D(C1) ^ key and the third to D(C1),
+// so XORing them yields the key. The original blocks after C1 follow, so the final block still
+// carries valid padding.
+pub fn recover_key_as_iv<R: CbcReceiver>(receiver: &R, ciphertext: &[u8]) -> Result<Vec<u8>> {
+    let block_size = Aes::BLOCK_SIZE;
+    if ciphertext.len() < 2 * block_size {
+        return Err(Error::AttackFailed("need at least two ciphertext blocks"));
+    }
+
+    let first = &ciphertext[..block_size];
+    let mut modified = first.to_vec();
+    modified.extend(vec![0u8; block_size]);
+    modified.extend_from_slice(first);
+    modified.extend_from_slice(&ciphertext[block_size..]);
+
+    match receiver.receive(&modified) {
+        Err(Error::NonAsciiPlaintext(plaintext)) => Ok(plaintext[..block_size]
+            .iter()
+            .zip(&plaintext[2 * block_size..3 * block_size])
+            .map(|(a, b)| a ^ b)
+            .collect()),
+        Err(err) => Err(err),
+        Ok(()) => Err(Error::AttackFailed(
+            "receiver accepted the modified ciphertext",
+        )),
+    }
+}
@@ -0,0 +1,44 @@
+use crate::cipher::Aes;
+use crate::modes::CipherMode;
+use crate::modes::Ctr;
+use crate::utils::random_bytes;
+use crate::Result;
+
+pub trait EditOracle {
+    fn edit(&self, ciphertext: &[u8], offset: usize, newtext: &[u8]) -> Result<Vec<u8>>;
+}
+
+// Encrypts under a key it never reveals, but lets anyone rewrite part of a ciphertext, the way a
+// seekable disk-encryption layer would.
+pub struct CtrEditService {
+    ctr: Ctr<Aes>,
+}
+
+impl CtrEditService {
+    pub fn new() -> Result<CtrEditService> {
+        Ok(CtrEditService {
+            ctr: Ctr::new(Aes::new(&random_bytes(16))?, rand::random())?,
+        })
+    }
+
+    pub fn encrypt(&self, plaintext: &[u8]) -> Result<Vec<u8>> {
+        self.ctr.encrypt(plaintext)
+    }
+}
+
+impl EditOracle for CtrEditService {
+    fn edit(&self, ciphertext: &[u8], offset: usize, newtext: &[u8]) -> Result<Vec<u8>> {
+        self.ctr.edit(ciphertext, offset, newtext)
+    }
+}
+
+// Writing zeros over the whole ciphertext hands back the raw keystream.
+pub fn recover_plaintext_via_edit<O: EditOracle>(oracle: &O, ciphertext: &[u8]) -> Result<Vec<u8>> {
+    let keystream = oracle.edit(ciphertext, 0, &vec![0u8; ciphertext.len()])?;
+
+    Ok(ciphertext
+        .iter()
+        .zip(keystream)
+        .map(|(c, k)| c ^ k)
+        .collect())
+}
@@ -0,0 +1,116 @@
+use crate::attacks::mode_detection::EncryptionOracle;
+use crate::cipher::Aes;
+use crate::encoding::encode_kv;
+use crate::encoding::parse_kv;
+use crate::modes::CipherMode;
+use crate::modes::Ecb;
+use crate::padding::pad_pkcs7;
+use crate::utils::random_bytes;
+use crate::Error;
+use crate::Result;
+
+const EMAIL_PREFIX: &str = "email=";
+const ROLE_PREFIX: &str = "&uid=10&role=";
+
+pub fn profile_for(email: &str) -> String {
+    encode_kv(&[("email", email), ("uid", "10"), ("role", "user")])
+}
+
+pub struct ProfileService {
+    ecb: Ecb<Aes>,
+}
+
+impl ProfileService {
+    pub fn new() -> Result<ProfileService> {
+        Ok(ProfileService {
+            ecb: Ecb::new(Aes::new(&random_bytes(16))?),
+        })
+    }
+
+    pub fn encrypt_profile(&self, email: &str) -> Result<Vec<u8>> {
+        self.ecb.encrypt(profile_for(email).as_bytes())
+    }
+
+    pub fn decrypt_profile(&self, ciphertext: &[u8]) -> Result<Vec<(String, String)>> {
+        let plaintext = String::from_utf8(self.ecb.decrypt(ciphertext)?)?;
+        parse_kv(&plaintext)
+    }
+
+    pub fn is_admin(&self, ciphertext: &[u8]) -> Result<bool> {
+        Ok(self
+            .decrypt_profile(ciphertext)?
+            .iter()
+            .any(|(key, value)| key == "role" && value == "admin"))
+    }
+}
+
+impl EncryptionOracle for ProfileService {
+    fn encrypt(&self, input: &[u8]) -> Result<Vec<u8>> {
+        self.encrypt_profile(&String::from_utf8(input.to_vec())?)
+    }
+}
+
+// Concatenates the chosen (ciphertext, block index) pairs into a single ciphertext.
+pub fn splice_blocks(blocks: &[(&[u8], usize)], block_size: usize) -> Result<Vec<u8>> {
+    let mut out = Vec::with_capacity(blocks.len() * block_size);
+
+    for (ciphertext, index) in blocks {
+        let block = ciphertext
+            .get(index * block_size..(index + 1) * block_size)
+            .ok_or(Error::AttackFailed("block index out of range"))?;
+        out.extend_from_slice(block);
+    }
+
+    Ok(out)
+}
+
+// Encrypts a block holding a PKCS#7-padded "admin" on its own, then swaps it in for the final
+// block of a profile whose role value has been pushed onto a fresh block boundary.
+pub fn forge_admin_profile<O: EncryptionOracle>(oracle: &O, block_size: usize) -> Result<Vec<u8>> {
+    // The padded "admin" has to fit in one block, and PKCS#7 can't describe more than 255.
+    if block_size <= b"admin".len() || block_size > u8::MAX as usize {
+        return Err(Error::InvalidBlockSize(block_size));
+    }
+
+    let filler = (block_size - EMAIL_PREFIX.len() % block_size) % block_size;
+    let mut admin_email = vec![b'A'; filler];
+    let mut admin_block = b"admin".to_vec();
+    pad_pkcs7(&mut admin_block, block_size as u8)?;
+    admin_email.extend(admin_block);
+
+    let admin_ciphertext = oracle.encrypt(&admin_email)?;
+    let admin_index = (EMAIL_PREFIX.len() + filler) / block_size;
+
+    let mut email = b"a@b.".to_vec();
+    let fixed_len = EMAIL_PREFIX.len() + ROLE_PREFIX.len() + email.len();
+    let padding = (block_size - fixed_len % block_size) % block_size;
+    email.extend(vec![b'm'; padding]);
+
+    let profile_ciphertext = oracle.encrypt(&email)?;
+    let role_index = (EMAIL_PREFIX.len() + email.len() + ROLE_PREFIX.len()) / block_size;
+
+    let mut blocks = (0..role_index)
+        .map(|index| (&profile_ciphertext[..], index))
+        .collect::<Vec<_>>();
+    blocks.push((&admin_ciphertext[..], admin_index));
+
+    splice_blocks(&blocks, block_size)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ProfileService;
+    use crate::Error;
+
+    #[test]
+    fn forge_admin_profile_rejects_unusable_block_sizes() {
+        let service = ProfileService::new().unwrap();
+
+        for block_size in [0, 5, 256].iter() {
+            assert!(matches!(
+                super::forge_admin_profile(&service, *block_size),
+                Err(Error::InvalidBlockSize(_))
+            ));
+        }
+    }
+}
@@ -0,0 +1,196 @@
+use crate::dh::session_key;
+use crate::dh::DiffieHellman;
+use crate::dh::Group;
+use crate::modes::aes_128_cbc_crypter;
+use crate::modes::Mode;
+use crate::network::Actor;
+use crate::network::Direction;
+use crate::network::Initiator;
+use crate::network::Relay;
+use crate::utils::random_bytes;
+use crate::Error;
+use crate::Result;
+use num_bigint::BigUint;
+
+#[derive(Clone, Debug)]
+pub enum DhMessage {
+    Negotiate {
+        p: BigUint,
+        g: BigUint,
+        public_key: BigUint,
+    },
+    Accept {
+        public_key: BigUint,
+    },
+    // AES-CBC under the session key, with the IV sent alongside.
+    Data {
+        ciphertext: Vec<u8>,
+        iv: Vec<u8>,
+    },
+}
+
+fn seal(key: &[u8], plaintext: &[u8]) -> Result<DhMessage> {
+    let iv = random_bytes(16);
+    Ok(DhMessage::Data {
+        ciphertext: aes_128_cbc_crypter(key, &iv, plaintext, Mode::Encrypt)?,
+        iv,
+    })
+}
+
+// A: negotiates a key, then sends each message in turn and checks B echoes it back.
+pub struct EchoClient {
+    dh: DiffieHellman,
+    key: Option<Vec<u8>>,
+    pending: Vec<Vec<u8>>,
+    echoed: Vec<Vec<u8>>,
+}
+
+impl EchoClient {
+    pub fn new(group: Group, messages: Vec<Vec<u8>>) -> EchoClient {
+        let mut pending = messages;
+        pending.reverse();
+
+        EchoClient {
+            dh: DiffieHellman::new(group),
+            key: None,
+            pending,
+            echoed: vec![],
+        }
+    }
+
+    pub fn echoed(&self) -> &[Vec<u8>] {
+        &self.echoed
+    }
+
+    fn send_next(&mut self, key: &[u8]) -> Result<Option<DhMessage>> {
+        match self.pending.last() {
+            Some(plaintext) => Ok(Some(seal(key, plaintext)?)),
+            None => Ok(None),
+        }
+    }
+}
+
+impl Initiator<DhMessage> for EchoClient {
+    fn start(&mut self) -> Result<DhMessage> {
+        let group = self.dh.group();
+        Ok(DhMessage::Negotiate {
+            p: group.p.clone(),
+            g: group.g.clone(),
+            public_key: self.dh.public_key().clone(),
+        })
+    }
+}
+
+impl Actor<DhMessage> for EchoClient {
+    fn receive(&mut self, message: DhMessage) -> Result<Option<DhMessage>> {
+        match (message, self.key.clone()) {
+            (DhMessage::Accept { public_key }, None) => {
+                let key = self.dh.session_key(&public_key);
+                self.key = Some(key.clone());
+                self.send_next(&key)
+            }
+            (DhMessage::Data { ciphertext, iv }, Some(key)) => {
+                let plaintext = aes_128_cbc_crypter(&key, &iv, &ciphertext, Mode::Decrypt)?;
+                if self.pending.pop().as_ref() != Some(&plaintext) {
+                    return Err(Error::Protocol("echo does not match what was sent"));
+                }
+
+                self.echoed.push(plaintext);
+                self.send_next(&key)
+            }
+            _ => Err(Error::Protocol("unexpected message")),
+        }
+    }
+}
+
+// B: accepts whatever group it is offered and echoes every message under a fresh IV.
+pub struct EchoServer {
+    key: Option<Vec<u8>>,
+}
+
+impl EchoServer {
+    pub fn new() -> EchoServer {
+        EchoServer { key: None }
+    }
+}
+
+impl Default for EchoServer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Actor<DhMessage> for EchoServer {
+    fn receive(&mut self, message: DhMessage) -> Result<Option<DhMessage>> {
+        match (message, &self.key) {
+            (DhMessage::Negotiate { p, g, public_key }, None) => {
+                let dh = DiffieHellman::new(Group::new(p, g)?);
+                self.key = Some(dh.session_key(&public_key));
+
+                Ok(Some(DhMessage::Accept {
+                    public_key: dh.public_key().clone(),
+                }))
+            }
+            (DhMessage::Data { ciphertext, iv }, Some(key)) => {
+                let plaintext = aes_128_cbc_crypter(key, &iv, &ciphertext, Mode::Decrypt)?;
+                Ok(Some(seal(key, &plaintext)?))
+            }
+            _ => Err(Error::Protocol("unexpected message")),
+        }
+    }
+}
+
+// M: hands each side p in place of the other's public key. Then both compute p^x mod p = 0,
+// so the session key is known without ever learning a private key. The data is passed on
+// untouched after being read.
+pub struct KeyFixingAttacker {
+    p: Option<BigUint>,
+    intercepted: Vec<Vec<u8>>,
+}
+
+impl KeyFixingAttacker {
+    pub fn new() -> KeyFixingAttacker {
+        KeyFixingAttacker {
+            p: None,
+            intercepted: vec![],
+        }
+    }
+
+    pub fn intercepted(&self) -> &[Vec<u8>] {
+        &self.intercepted
+    }
+}
+
+impl Default for KeyFixingAttacker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Relay<DhMessage> for KeyFixingAttacker {
+    fn relay(&mut self, message: DhMessage, _: Direction) -> Result<DhMessage> {
+        match message {
+            DhMessage::Negotiate { p, g, .. } => {
+                self.p = Some(p.clone());
+                Ok(DhMessage::Negotiate {
+                    public_key: p.clone(),
+                    p,
+                    g,
+                })
+            }
+            DhMessage::Accept { .. } => match &self.p {
+                Some(p) => Ok(DhMessage::Accept {
+                    public_key: p.clone(),
+                }),
+                None => Err(Error::Protocol("accept before negotiation")),
+            },
+            DhMessage::Data { ciphertext, iv } => {
+                let key = session_key(&BigUint::from(0u32));
+                self.intercepted
+                    .push(aes_128_cbc_crypter(&key, &iv, &ciphertext, Mode::Decrypt)?);
+
+                Ok(DhMessage::Data { ciphertext, iv })
+            }
+        }
+    }
+}
@@ -0,0 +1,93 @@
+use crate::attacks::xor::solve_repeating_key;
+use crate::Error;
+use crate::Result;
+
+// Recovers a CTR keystream that was reused across many messages. Truncated to the length of the
+// shortest ciphertext, the messages are just repeating-key XOR with a key that long, so the
+// statistical pass solves each keystream column as single-byte XOR. Known plaintext can then be
+// pinned to correct those guesses or to reach past the common length.
+pub struct FixedNonceCtrBreaker {
+    ciphertexts: Vec<Vec<u8>>,
+    keystream: Vec<Option<u8>>,
+}
+
+impl FixedNonceCtrBreaker {
+    pub fn new(ciphertexts: Vec<Vec<u8>>) -> FixedNonceCtrBreaker {
+        let max_len = ciphertexts.iter().map(Vec::len).max().unwrap_or(0);
+
+        FixedNonceCtrBreaker {
+            ciphertexts,
+            keystream: vec![None; max_len],
+        }
+    }
+
+    pub fn common_len(&self) -> usize {
+        self.ciphertexts.iter().map(Vec::len).min().unwrap_or(0)
+    }
+
+    pub fn solve_statistically(&mut self) -> Result<()> {
+        let common_len = self.common_len();
+        if common_len == 0 {
+            return Err(Error::AttackFailed("no ciphertext bytes in common"));
+        }
+
+        let truncated = self
+            .ciphertexts
+            .iter()
+            .flat_map(|ciphertext| ciphertext[..common_len].iter().copied())
+            .collect::<Vec<_>>();
+
+        let (keystream, _) = solve_repeating_key(&truncated, common_len)?;
+        for (slot, byte) in self.keystream.iter_mut().zip(keystream) {
+            *slot = Some(byte);
+        }
+
+        Ok(())
+    }
+
+    // Declares that ciphertext `index` decrypts to `known` at `offset`, which fixes the keystream
+    // at those positions for every other ciphertext too.
+    pub fn pin(&mut self, index: usize, offset: usize, known: &[u8]) -> Result<()> {
+        let ciphertext = self
+            .ciphertexts
+            .get(index)
+            .ok_or(Error::AttackFailed("ciphertext index out of range"))?;
+        let segment =
+            ciphertext
+                .get(offset..offset + known.len())
+                .ok_or(Error::LengthMismatch {
+                    expected: ciphertext.len().saturating_sub(offset),
+                    actual: known.len(),
+                })?;
+
+        for (i, (c, p)) in segment.iter().zip(known).enumerate() {
+            self.keystream[offset + i] = Some(c ^ p);
+        }
+
+        Ok(())
+    }
+
+    // The keystream up to the first position that is still unknown.
+    pub fn keystream(&self) -> Vec<u8> {
+        self.keystream.iter().map_while(|byte| *byte).collect()
+    }
+
+    pub fn plaintext(&self, index: usize) -> Option<Vec<u8>> {
+        let keystream = self.keystream();
+        let ciphertext = self.ciphertexts.get(index)?;
+
+        Some(
+            ciphertext
+                .iter()
+                .zip(keystream)
+                .map(|(c, k)| c ^ k)
+                .collect(),
+        )
+    }
+
+    pub fn plaintexts(&self) -> Vec<Vec<u8>> {
+        (0..self.ciphertexts.len())
+            .filter_map(|index| self.plaintext(index))
+            .collect()
+    }
+}
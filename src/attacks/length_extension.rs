@@ -0,0 +1,99 @@
+use crate::hash::Hash;
+use crate::hash::Md4;
+use crate::hash::Sha1;
+use crate::mac::keyed_mac;
+use crate::utils::random_bytes;
+use crate::Error;
+use crate::Result;
+use rand::Rng;
+use std::marker::PhantomData;
+
+pub trait MacOracle {
+    fn verify(&self, message: &[u8], mac: &[u8]) -> bool;
+}
+
+// Signs messages with H(key || message) under a random key of 1-64 bytes.
+pub struct KeyedMacService<H> {
+    key: Vec<u8>,
+    hash: PhantomData<H>,
+}
+
+pub type Sha1MacService = KeyedMacService<Sha1>;
+pub type Md4MacService = KeyedMacService<Md4>;
+
+impl<H: Hash> KeyedMacService<H> {
+    pub fn new() -> KeyedMacService<H> {
+        KeyedMacService {
+            key: random_bytes(rand::thread_rng().gen_range(1..=64)),
+            hash: PhantomData,
+        }
+    }
+
+    pub fn mac(&self, message: &[u8]) -> Vec<u8> {
+        keyed_mac::<H>(&self.key, message)
+    }
+}
+
+impl<H: Hash> Default for KeyedMacService<H> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<H: Hash> MacOracle for KeyedMacService<H> {
+    fn verify(&self, message: &[u8], mac: &[u8]) -> bool {
+        self.mac(message) == mac
+    }
+}
+
+// The MAC is the hash's state after key || message || glue, so hashing can resume from it as
+// long as the length counter accounts for the guessed key. Returns message || glue || suffix
+// and its MAC under the unknown key.
+pub fn length_extend<H: Hash>(
+    mac: &[u8],
+    original_message: &[u8],
+    key_len_guess: usize,
+    suffix: &[u8],
+) -> Result<(Vec<u8>, Vec<u8>)> {
+    let glue = H::padding((key_len_guess + original_message.len()) as u64);
+    let processed_len = key_len_guess + original_message.len() + glue.len();
+
+    let mut hash = H::resume(mac, processed_len as u64)?;
+    hash.update(suffix);
+
+    let mut forged = original_message.to_vec();
+    forged.extend(glue);
+    forged.extend_from_slice(suffix);
+
+    Ok((forged, hash.finalize_to_vec()))
+}
+
+pub fn sha1_length_extend(
+    mac: &[u8],
+    original_message: &[u8],
+    key_len_guess: usize,
+    suffix: &[u8],
+) -> Result<(Vec<u8>, Vec<u8>)> {
+    length_extend::<Sha1>(mac, original_message, key_len_guess, suffix)
+}
+
+// Tries each key length up to `max_key_len` until the oracle accepts the forgery, returning the
+// forged message, its MAC and the key length that worked.
+pub fn forge_extension<H: Hash, O: MacOracle>(
+    oracle: &O,
+    mac: &[u8],
+    original_message: &[u8],
+    suffix: &[u8],
+    max_key_len: usize,
+) -> Result<(Vec<u8>, Vec<u8>, usize)> {
+    for key_len in 0..=max_key_len {
+        let (forged, forged_mac) = length_extend::<H>(mac, original_message, key_len, suffix)?;
+        if oracle.verify(&forged, &forged_mac) {
+            return Ok((forged, forged_mac, key_len));
+        }
+    }
+
+    Err(Error::AttackFailed(
+        "no key length produced a valid forgery",
+    ))
+}
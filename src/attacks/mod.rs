@@ -0,0 +1,15 @@
+pub mod bitflip;
+pub mod byte_at_a_time;
+pub mod cbc_key_as_iv;
+pub mod ctr_edit;
+pub mod cut_and_paste;
+pub mod dh_mitm;
+pub mod fixed_nonce_ctr;
+pub mod length_extension;
+pub mod mode_detection;
+pub mod mt19937_clone;
+pub mod mt19937_seed;
+pub mod mt19937_stream;
+pub mod padding_oracle;
+pub mod timing_leak;
+pub mod xor;
@@ -0,0 +1,136 @@
+use crate::cipher::Aes;
+use crate::modes::Cbc;
+use crate::modes::CipherMode;
+use crate::modes::Ecb;
+use crate::utils::random_bytes;
+use crate::Result;
+use rand::Rng;
+use std::collections::HashSet;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BlockMode {
+    Ecb,
+    Cbc,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Detection {
+    pub mode: BlockMode,
+    pub confidence: f64,
+}
+
+pub trait EncryptionOracle {
+    fn encrypt(&self, input: &[u8]) -> Result<Vec<u8>>;
+}
+
+// Challenge 11: picks ECB or CBC under a random key (and IV) when created, then wraps every
+// input in 5-10 random bytes on either side before encrypting it.
+pub struct RandomModeOracle {
+    mode: BlockMode,
+    key: Vec<u8>,
+    iv: Vec<u8>,
+}
+
+impl RandomModeOracle {
+    pub fn new() -> RandomModeOracle {
+        let mode = if rand::thread_rng().gen() {
+            BlockMode::Ecb
+        } else {
+            BlockMode::Cbc
+        };
+
+        RandomModeOracle {
+            mode,
+            key: random_bytes(16),
+            iv: random_bytes(16),
+        }
+    }
+
+    pub fn mode(&self) -> BlockMode {
+        self.mode
+    }
+}
+
+impl Default for RandomModeOracle {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl EncryptionOracle for RandomModeOracle {
+    fn encrypt(&self, input: &[u8]) -> Result<Vec<u8>> {
+        let mut rng = rand::thread_rng();
+        let mut plaintext = random_bytes(rng.gen_range(5..=10));
+        plaintext.extend_from_slice(input);
+        plaintext.extend(random_bytes(rng.gen_range(5..=10)));
+
+        let cipher = Aes::new(&self.key)?;
+        match self.mode {
+            BlockMode::Ecb => Ecb::new(cipher).encrypt(&plaintext),
+            BlockMode::Cbc => Cbc::new(cipher, &self.iv)?.encrypt(&plaintext),
+        }
+    }
+}
+
+// Identical input blocks always cover all but one of themselves as whole ciphertext blocks,
+// whatever the oracle puts around them, so ECB is guaranteed to repeat at least
+// PROBE_BLOCKS - 2 of its output blocks.
+const PROBE_BLOCKS: usize = 4;
+
+// ECB confidence is the share of those guaranteed repeats that actually turned up, so one stray
+// repeat is only half-convincing. No repeat at all is something ECB cannot produce, so that is
+// reported as CBC with full confidence.
+pub fn detect_block_mode<O: EncryptionOracle>(oracle: &O) -> Result<Detection> {
+    let ciphertext = oracle.encrypt(&[0u8; PROBE_BLOCKS * Aes::BLOCK_SIZE])?;
+
+    let blocks = ciphertext.chunks(Aes::BLOCK_SIZE).collect::<Vec<_>>();
+    let distinct = blocks.iter().collect::<HashSet<_>>().len();
+    let repeats = blocks.len() - distinct;
+
+    if repeats > 0 {
+        let guaranteed = PROBE_BLOCKS - 2;
+        Ok(Detection {
+            mode: BlockMode::Ecb,
+            confidence: repeats.min(guaranteed) as f64 / guaranteed as f64,
+        })
+    } else {
+        Ok(Detection {
+            mode: BlockMode::Cbc,
+            confidence: 1.0,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::detect_block_mode;
+    use super::BlockMode;
+    use super::EncryptionOracle;
+    use crate::Result;
+
+    // Echoes its input back, so the caller controls exactly which blocks repeat.
+    struct Identity(Vec<u8>);
+
+    impl EncryptionOracle for Identity {
+        fn encrypt(&self, _: &[u8]) -> Result<Vec<u8>> {
+            Ok(self.0.clone())
+        }
+    }
+
+    #[test]
+    fn confidence_tracks_repeats() {
+        let mut one_repeat = vec![0u8; 32];
+        one_repeat.extend((0..32).collect::<Vec<u8>>());
+        let detection = detect_block_mode(&Identity(one_repeat)).unwrap();
+        assert_eq!(detection.mode, BlockMode::Ecb);
+        assert_eq!(detection.confidence, 0.5);
+
+        let detection = detect_block_mode(&Identity(vec![0u8; 64])).unwrap();
+        assert_eq!(detection.mode, BlockMode::Ecb);
+        assert_eq!(detection.confidence, 1.0);
+
+        let detection = detect_block_mode(&Identity((0..64).collect())).unwrap();
+        assert_eq!(detection.mode, BlockMode::Cbc);
+        assert_eq!(detection.confidence, 1.0);
+    }
+}
@@ -0,0 +1,251 @@
+use crate::rng::mt19937::temper;
+use crate::rng::mt19937::M;
+use crate::rng::mt19937::MATRIX_A;
+use crate::rng::mt19937::N;
+use crate::rng::Mt19937;
+use crate::Error;
+use crate::Result;
+
+fn undo_right_shift_xor(y: u32, shift: u32) -> u32 {
+    let mut x = y;
+    for _ in 0..32 / shift {
+        x = y ^ (x >> shift);
+    }
+    x
+}
+
+fn undo_left_shift_xor_and(y: u32, shift: u32, mask: u32) -> u32 {
+    let mut x = y;
+    for _ in 0..32 / shift {
+        x = y ^ ((x << shift) & mask);
+    }
+    x
+}
+
+pub fn untemper(mut y: u32) -> u32 {
+    y = undo_right_shift_xor(y, 18);
+    y = undo_left_shift_xor_and(y, 15, 0xefc6_0000);
+    y = undo_left_shift_xor_and(y, 7, 0x9d2c_5680);
+    undo_right_shift_xor(y, 11)
+}
+
+// 624 consecutive outputs untemper straight back into the state that produced them, so the
+// clone picks up where the original left off.
+pub fn clone_from_outputs(outputs: &[u32; N]) -> Mt19937 {
+    let mut state = [0u32; N];
+    for (word, output) in state.iter_mut().zip(outputs.iter()) {
+        *word = untemper(*output);
+    }
+
+    Mt19937::from_state(state, N)
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PartialOutput {
+    // Position of the output in the stream, counting from the first one the clone reproduces.
+    pub index: usize,
+    pub value: u32,
+    // Bits of `value` that were actually observed.
+    pub mask: u32,
+}
+
+const VARIABLES: usize = N * 32;
+const ROW_WORDS: usize = VARIABLES / 64;
+
+type Row = Vec<u64>;
+
+fn unit_row(variable: usize) -> Row {
+    let mut row = vec![0u64; ROW_WORDS];
+    row[variable / 64] |= 1 << (variable % 64);
+    row
+}
+
+fn xor_row(target: &mut Row, source: &Row, from_word: usize) {
+    for (a, b) in target[from_word..].iter_mut().zip(&source[from_word..]) {
+        *a ^= b;
+    }
+}
+
+// Every state bit as a linear combination of the bits of the initial state, stored as one row
+// per bit of each word.
+struct SymbolicState {
+    words: Vec<Vec<Row>>,
+}
+
+impl SymbolicState {
+    fn new() -> SymbolicState {
+        let words = (0..N)
+            .map(|word| (0..32).map(|bit| unit_row(32 * word + bit)).collect())
+            .collect();
+
+        SymbolicState { words }
+    }
+
+    // The twist is linear over GF(2): the conditional XOR with MATRIX_A is just the low bit of
+    // y multiplied into each set bit of the constant.
+    fn twist(&mut self) {
+        for i in 0..N {
+            let mut y = self.words[(i + 1) % N][..31].to_vec();
+            y.push(self.words[i][31].clone());
+
+            let mut next = self.words[(i + M) % N].clone();
+            for (bit, row) in next.iter_mut().enumerate() {
+                if bit < 31 {
+                    xor_row(row, &y[bit + 1], 0);
+                }
+                if MATRIX_A >> bit & 1 == 1 {
+                    xor_row(row, &y[0], 0);
+                }
+            }
+
+            self.words[i] = next;
+        }
+    }
+
+    fn tempered_bit(&self, word: usize, bit: usize) -> Row {
+        let mut row = vec![0u64; ROW_WORDS];
+        for source in 0..32 {
+            if temper(1 << source) >> bit & 1 == 1 {
+                xor_row(&mut row, &self.words[word][source], 0);
+            }
+        }
+        row
+    }
+}
+
+// Gaussian elimination done incrementally: each basis row is keyed by its lowest set variable,
+// so reducing a new equation only ever moves its lowest bit upwards.
+struct LinearSystem {
+    basis: Vec<Option<(Row, bool)>>,
+}
+
+impl LinearSystem {
+    fn new() -> LinearSystem {
+        LinearSystem {
+            basis: vec![None; VARIABLES],
+        }
+    }
+
+    fn insert(&mut self, mut row: Row, mut rhs: bool) -> Result<()> {
+        let mut word = 0;
+
+        loop {
+            while word < ROW_WORDS && row[word] == 0 {
+                word += 1;
+            }
+
+            if word == ROW_WORDS {
+                return if rhs {
+                    Err(Error::AttackFailed("inconsistent observations"))
+                } else {
+                    Ok(())
+                };
+            }
+
+            let pivot = 64 * word + row[word].trailing_zeros() as usize;
+            match &self.basis[pivot] {
+                Some((basis_row, basis_rhs)) => {
+                    xor_row(&mut row, basis_row, word);
+                    rhs ^= basis_rhs;
+                }
+                None => {
+                    self.basis[pivot] = Some((row, rhs));
+                    return Ok(());
+                }
+            }
+        }
+    }
+
+    // Back-substitutes from the highest pivot down; variables the observations never pinned
+    // down are left at zero.
+    fn solve(&self) -> Row {
+        let mut solution = vec![0u64; ROW_WORDS];
+
+        for pivot in (0..VARIABLES).rev() {
+            if let Some((row, rhs)) = &self.basis[pivot] {
+                let parity = row[pivot / 64..]
+                    .iter()
+                    .zip(&solution[pivot / 64..])
+                    .map(|(a, b)| (a & b).count_ones())
+                    .sum::<u32>()
+                    & 1;
+
+                if (parity == 1) != *rhs {
+                    solution[pivot / 64] |= 1 << (pivot % 64);
+                }
+            }
+        }
+
+        solution
+    }
+}
+
+// Every output bit is a linear function of the 19968 bits of the initial state, so each
+// observed bit is one equation over GF(2). Missing outputs and unknown bits just mean fewer
+// equations, and outputs from later in the stream are reached by twisting the symbolic state.
+pub fn clone_from_partial_outputs(observations: &[PartialOutput]) -> Result<Mt19937> {
+    let mut observations = observations.to_vec();
+    observations.sort_by_key(|observation| observation.index);
+
+    let mut symbolic = SymbolicState::new();
+    let mut generation = 0;
+    let mut system = LinearSystem::new();
+
+    for observation in &observations {
+        while generation < observation.index / N {
+            symbolic.twist();
+            generation += 1;
+        }
+
+        for bit in 0..32 {
+            if observation.mask >> bit & 1 == 1 {
+                let row = symbolic.tempered_bit(observation.index % N, bit);
+                system.insert(row, observation.value >> bit & 1 == 1)?;
+            }
+        }
+    }
+
+    // The low 31 bits of the first word never feed into the twist, so only the first output
+    // depends on them. Any other bit left free would make the predicted stream a guess.
+    if (31..VARIABLES).any(|variable| system.basis[variable].is_none()) {
+        return Err(Error::AttackFailed(
+            "observations do not determine the state",
+        ));
+    }
+
+    let solution = system.solve();
+    let mut state = [0u32; N];
+    for (i, word) in state.iter_mut().enumerate() {
+        *word = (solution[i / 2] >> (32 * (i % 2))) as u32;
+    }
+
+    Ok(Mt19937::from_state(state, 0))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::PartialOutput;
+    use crate::rng::mt19937::temper;
+    use crate::rng::Mt19937;
+
+    #[test]
+    fn untemper_inverts_temper() {
+        for y in [0u32, 1, 0xdead_beef, 0x8000_0000, u32::MAX, 123_456_789].iter() {
+            assert_eq!(super::untemper(temper(*y)), *y);
+        }
+    }
+
+    #[test]
+    fn too_few_observations() {
+        let mut mt = Mt19937::new(5489);
+        let observations = (0..100)
+            .map(|index| PartialOutput {
+                index,
+                value: mt.next_u32(),
+                mask: u32::MAX,
+            })
+            .collect::<Vec<_>>();
+
+        assert!(super::clone_from_partial_outputs(&observations).is_err());
+    }
+}
@@ -0,0 +1,37 @@
+use crate::clock::Clock;
+use crate::rng::Mt19937;
+use std::ops::RangeInclusive;
+
+// Tries every Unix timestamp in `window` as a seed, newest first. Seeds are 32 bits, so the
+// window is clamped to `u32::MAX` before walking it.
+pub fn crack_timestamp_seed(first_output: u32, window: RangeInclusive<u64>) -> Option<u32> {
+    (*window.start()..=(*window.end()).min(u32::MAX as u64))
+        .rev()
+        .map(|timestamp| timestamp as u32)
+        .find(|seed| Mt19937::new(*seed).next_u32() == first_output)
+}
+
+pub fn crack_recent_seed<C: Clock>(first_output: u32, clock: &C, max_age_secs: u64) -> Option<u32> {
+    let now = clock.unix_timestamp();
+    crack_timestamp_seed(first_output, now.saturating_sub(max_age_secs)..=now)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::rng::Mt19937;
+
+    #[test]
+    fn window_past_u32_max() {
+        let seed = u32::MAX - 50;
+        let first_output = Mt19937::new(seed).next_u32();
+
+        assert_eq!(
+            super::crack_timestamp_seed(first_output, (u32::MAX as u64 - 100)..=u64::MAX),
+            Some(seed)
+        );
+        assert_eq!(
+            super::crack_timestamp_seed(first_output, (u32::MAX as u64 + 1)..=u64::MAX),
+            None
+        );
+    }
+}
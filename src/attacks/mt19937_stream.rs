@@ -0,0 +1,72 @@
+use crate::cipher::mt19937_stream::mt19937_keystream;
+use crate::cipher::mt19937_stream_cipher;
+use crate::clock::Clock;
+use crate::utils::random_bytes;
+use rand::Rng;
+
+const TOKEN_LEN: usize = 16;
+
+// Challenge 24: a random number of random bytes followed by the caller's plaintext, encrypted
+// under a random 16-bit seed.
+pub fn encrypt_with_random_prefix(seed: u16, plaintext: &[u8]) -> Vec<u8> {
+    let mut data = random_bytes(rand::thread_rng().gen_range(5..=40));
+    data.extend_from_slice(plaintext);
+    mt19937_stream_cipher(seed, &mut data);
+    data
+}
+
+// The known plaintext sits at the end of the ciphertext, so only that part of each candidate
+// keystream needs comparing.
+pub fn recover_mt19937_stream_seed(ciphertext: &[u8], known_suffix: &[u8]) -> Option<u16> {
+    // An empty suffix would match every seed.
+    if known_suffix.is_empty() || known_suffix.len() > ciphertext.len() {
+        return None;
+    }
+
+    let offset = ciphertext.len() - known_suffix.len();
+    let expected = ciphertext[offset..]
+        .iter()
+        .zip(known_suffix)
+        .map(|(c, p)| c ^ p)
+        .collect::<Vec<_>>();
+
+    (0..=u16::MAX)
+        .find(|seed| mt19937_keystream(*seed as u32, ciphertext.len())[offset..] == expected[..])
+}
+
+pub fn reset_token<C: Clock>(clock: &C) -> Vec<u8> {
+    mt19937_keystream(clock.unix_timestamp() as u32, TOKEN_LEN)
+}
+
+// True if `token` is what an MT19937 seeded with a timestamp from the last `max_age_secs`
+// seconds would have produced.
+pub fn is_time_seeded_token<C: Clock>(token: &[u8], clock: &C, max_age_secs: u64) -> bool {
+    // Every seed produces the empty keystream.
+    if token.is_empty() {
+        return false;
+    }
+
+    let now = clock.unix_timestamp();
+
+    (now.saturating_sub(max_age_secs)..=now)
+        .rev()
+        .any(|timestamp| mt19937_keystream(timestamp as u32, token.len()) == token)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::is_time_seeded_token;
+    use super::recover_mt19937_stream_seed;
+    use crate::clock::SimulatedClock;
+
+    #[test]
+    fn empty_suffix_recovers_nothing() {
+        assert_eq!(recover_mt19937_stream_seed(b"ciphertext", &[]), None);
+    }
+
+    #[test]
+    fn empty_token_is_not_time_seeded() {
+        let clock = SimulatedClock::at_unix_timestamp(1_600_000_000);
+        assert!(!is_time_seeded_token(&[], &clock, 10));
+    }
+}
@@ -0,0 +1,348 @@
+use crate::encoding::encode_query;
+use crate::encoding::parse_query;
+use crate::mac::hmac_sha1;
+use crate::utils::random_bytes;
+use crate::Error;
+use crate::Result;
+use std::cmp::Reverse;
+use std::collections::HashSet;
+use std::io::BufRead;
+use std::io::BufReader;
+use std::io::Read;
+use std::io::Write;
+use std::net::SocketAddr;
+use std::net::TcpListener;
+use std::net::TcpStream;
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+use std::thread;
+use std::thread::JoinHandle;
+use std::time::Duration;
+use std::time::Instant;
+
+// How many of the slowest candidates get re-measured before a byte is committed to.
+const CONTENDERS: usize = 16;
+
+// How many times a position is re-timed, each with twice the samples, before giving up on it.
+const RETRIES: usize = 3;
+
+// Bails out on the first mismatch after sleeping once per matching byte, so the response time
+// says how long the correct prefix is.
+pub fn insecure_compare(a: &[u8], b: &[u8], delay: Duration) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+
+    for (x, y) in a.iter().zip(b) {
+        if x != y {
+            return false;
+        }
+        thread::sleep(delay);
+    }
+
+    true
+}
+
+// Answers `GET /test?file=...&signature=...` on loopback with 200 when the hex signature is
+// the file name's HMAC-SHA1 (truncated to `mac_len` bytes) and 500 otherwise.
+pub struct HmacServer {
+    addr: SocketAddr,
+    shutdown: Arc<AtomicBool>,
+    handle: Option<JoinHandle<()>>,
+}
+
+impl HmacServer {
+    pub fn start(delay: Duration) -> Result<HmacServer> {
+        HmacServer::start_truncated(delay, 20)
+    }
+
+    pub fn start_truncated(delay: Duration, mac_len: usize) -> Result<HmacServer> {
+        let listener = TcpListener::bind("127.0.0.1:0")?;
+        let addr = listener.local_addr()?;
+        let shutdown = Arc::new(AtomicBool::new(false));
+        let key = random_bytes(16);
+
+        let stop = Arc::clone(&shutdown);
+        let handle = thread::spawn(move || {
+            for stream in listener.incoming() {
+                if stop.load(Ordering::SeqCst) {
+                    break;
+                }
+                if let Ok(stream) = stream {
+                    // A client hanging up mid-request is its own problem.
+                    let _ = serve(stream, &key, delay, mac_len);
+                }
+            }
+        });
+
+        Ok(HmacServer {
+            addr,
+            shutdown,
+            handle: Some(handle),
+        })
+    }
+
+    pub fn addr(&self) -> SocketAddr {
+        self.addr
+    }
+}
+
+impl Drop for HmacServer {
+    fn drop(&mut self) {
+        self.shutdown.store(true, Ordering::SeqCst);
+        // Wake the accept loop so it notices the flag.
+        let _ = TcpStream::connect(self.addr);
+        if let Some(handle) = self.handle.take() {
+            let _ = handle.join();
+        }
+    }
+}
+
+fn serve(stream: TcpStream, key: &[u8], delay: Duration, mac_len: usize) -> Result<()> {
+    let mut reader = BufReader::new(stream.try_clone()?);
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line)?;
+
+    let mut header = String::new();
+    while reader.read_line(&mut header)? > 2 {
+        header.clear();
+    }
+
+    let valid = match parse_request(&request_line) {
+        Ok((file, signature)) => {
+            let mac = hmac_sha1(key, file.as_bytes());
+            insecure_compare(&mac[..mac_len.min(mac.len())], &signature, delay)
+        }
+        Err(_) => false,
+    };
+
+    let status = if valid {
+        "200 OK"
+    } else {
+        "500 Internal Server Error"
+    };
+    write!(
+        &stream,
+        "HTTP/1.1 {}\r\nContent-Length: 0\r\nConnection: close\r\n\r\n",
+        status
+    )?;
+
+    Ok(())
+}
+
+fn parse_request(request_line: &str) -> Result<(String, Vec<u8>)> {
+    let target = request_line
+        .strip_prefix("GET ")
+        .and_then(|rest| rest.split(' ').next())
+        .ok_or(Error::Parse("not a GET request"))?;
+    let query = target
+        .strip_prefix("/test?")
+        .ok_or(Error::Parse("unknown path"))?;
+
+    let pairs = parse_query(query)?;
+    let lookup = |name: &str| {
+        pairs
+            .iter()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.clone())
+            .ok_or(Error::Parse("missing query parameter"))
+    };
+
+    Ok((lookup("file")?, hex::decode(lookup("signature")?)?))
+}
+
+pub trait SignatureOracle {
+    fn check(&self, signature: &[u8]) -> Result<bool>;
+}
+
+pub struct HmacClient {
+    addr: SocketAddr,
+    file: String,
+}
+
+impl HmacClient {
+    pub fn new(addr: SocketAddr, file: &str) -> HmacClient {
+        HmacClient {
+            addr,
+            file: file.to_string(),
+        }
+    }
+}
+
+impl SignatureOracle for HmacClient {
+    fn check(&self, signature: &[u8]) -> Result<bool> {
+        let mut stream = TcpStream::connect(self.addr)?;
+        let query = encode_query(&[("file", &self.file), ("signature", &hex::encode(signature))]);
+        write!(
+            stream,
+            "GET /test?{} HTTP/1.1\r\nHost: {}\r\nConnection: close\r\n\r\n",
+            query, self.addr
+        )?;
+
+        let mut response = String::new();
+        stream.read_to_string(&mut response)?;
+
+        match response.split(' ').nth(1) {
+            Some("200") => Ok(true),
+            Some(_) => Ok(false),
+            None => Err(Error::Parse("malformed HTTP response")),
+        }
+    }
+}
+
+// Times every candidate at `position` once per round, so a stall on the server lands on one
+// sample of many candidates rather than every sample of one, and keeps the median of each.
+fn median_timings<O: SignatureOracle>(
+    oracle: &O,
+    signature: &mut [u8],
+    position: usize,
+    candidates: &[u8],
+    rounds: usize,
+) -> Result<Vec<(u8, Duration)>> {
+    let mut samples = vec![Vec::with_capacity(rounds); candidates.len()];
+    for _ in 0..rounds.max(1) {
+        for (candidate, timings) in candidates.iter().zip(samples.iter_mut()) {
+            signature[position] = *candidate;
+            let start = Instant::now();
+            oracle.check(signature)?;
+            timings.push(start.elapsed());
+        }
+    }
+
+    Ok(candidates
+        .iter()
+        .zip(samples.iter_mut())
+        .map(|(candidate, timings)| {
+            timings.sort_unstable();
+            (*candidate, timings[timings.len() / 2])
+        })
+        .collect())
+}
+
+// Ranks every candidate not yet ruled out at `position`, then re-times the few slowest with
+// four times as many rounds alongside a middling candidate as the reference. Returns the
+// slowest and how far it stands above the reference, which is what a wrong guess costs.
+fn strongest_candidate<O: SignatureOracle>(
+    oracle: &O,
+    signature: &mut [u8],
+    position: usize,
+    samples: usize,
+    rejected: &HashSet<u8>,
+) -> Result<(u8, Duration)> {
+    let candidates = (0..=255u8)
+        .filter(|candidate| !rejected.contains(candidate))
+        .collect::<Vec<_>>();
+    if candidates.len() < 2 {
+        return Err(Error::AttackFailed("every candidate byte was ruled out"));
+    }
+
+    let mut ranked = median_timings(oracle, signature, position, &candidates, samples)?;
+    ranked.sort_unstable_by_key(|(_, timing)| Reverse(*timing));
+
+    let mut finalists = ranked
+        .iter()
+        .take(CONTENDERS)
+        .map(|(candidate, _)| *candidate)
+        .collect::<Vec<_>>();
+    finalists.push(ranked[ranked.len() / 2].0);
+
+    let timings = median_timings(oracle, signature, position, &finalists, samples * 4)?;
+    let reference = timings[timings.len() - 1].1;
+    let (candidate, timing) = timings[..timings.len() - 1]
+        .iter()
+        .max_by_key(|(_, timing)| *timing)
+        .copied()
+        .ok_or(Error::AttackFailed("no candidates were timed"))?;
+
+    Ok((candidate, timing.saturating_sub(reference)))
+}
+
+// Recovers the signature a byte at a time: the correct byte is the one whose comparison takes
+// one extra delay, so it should lead the others by about as much at every position. A lead
+// well short of the ones seen so far means either noise, so the position is timed again with
+// more samples, or an earlier wrong pick, so once retries run out the attack backs up and
+// rules that byte out. The final byte leaks nothing through timing, so it is brute-forced.
+pub fn recover_signature<O: SignatureOracle>(
+    oracle: &O,
+    signature_len: usize,
+    samples: usize,
+) -> Result<Vec<u8>> {
+    let mut signature = vec![0u8; signature_len];
+    let mut rejected = vec![HashSet::new(); signature_len];
+    let mut leads: Vec<Duration> = vec![];
+    let mut backtracks = 0;
+    let mut position = 0;
+
+    while position < signature_len {
+        let accepted = if position + 1 == signature_len {
+            let mut found = false;
+            for candidate in 0..=255u8 {
+                signature[position] = candidate;
+                if oracle.check(&signature)? {
+                    found = true;
+                    break;
+                }
+            }
+            found
+        } else {
+            let mut typical = leads.clone();
+            typical.sort_unstable();
+            let threshold = typical.get(typical.len() / 2).map(|lead| *lead / 2);
+
+            let mut accepted = false;
+            for attempt in 0..RETRIES {
+                let (candidate, lead) = strongest_candidate(
+                    oracle,
+                    &mut signature,
+                    position,
+                    samples << attempt,
+                    &rejected[position],
+                )?;
+
+                if threshold.is_none_or(|threshold| lead >= threshold) {
+                    signature[position] = candidate;
+                    leads.push(lead);
+                    accepted = true;
+                    break;
+                }
+            }
+            accepted
+        };
+
+        if accepted {
+            if position + 1 == signature_len {
+                return Ok(signature);
+            }
+            position += 1;
+            continue;
+        }
+
+        if position == 0 || backtracks == signature_len {
+            break;
+        }
+        backtracks += 1;
+
+        rejected[position].clear();
+        position -= 1;
+        leads.pop();
+        rejected[position].insert(signature[position]);
+    }
+
+    Err(Error::AttackFailed("recovered signature was rejected"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse_request;
+
+    #[test]
+    fn parse_request_decodes_url_escapes() {
+        let (file, signature) =
+            parse_request("GET /test?file=a%2Fb%20c&signature=00ff HTTP/1.1\r\n").unwrap();
+
+        assert_eq!(file, "a/b c");
+        assert_eq!(signature, [0x00, 0xff]);
+        assert!(parse_request("GET /other?file=a&signature=00 HTTP/1.1\r\n").is_err());
+    }
+}
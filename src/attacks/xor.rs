@@ -0,0 +1,142 @@
+use crate::analysis::hamming_distance;
+use crate::analysis::score_plaintext;
+use crate::xor::repeating_key_xor;
+use crate::Error;
+use crate::Result;
+use std::collections::HashMap;
+
+pub fn single_byte_xor_cipher(decoded: Vec<u8>) -> Result<(String, f64, u8)> {
+    let mut key = 0u8;
+    let mut score: f64 = 0.0;
+    let mut plaintext = String::from("");
+    'block: for x in 0..255u8 {
+        let mut char_count: HashMap<char, f64> = HashMap::new();
+        let decoded_len = decoded.len() as f64;
+        let mut decoded = decoded.clone();
+
+        for el in decoded.iter_mut() {
+            *el ^= x;
+
+            if (*el < 32 && *el != 9 && *el != 10 && *el != 13) || *el >= 127 {
+                continue 'block;
+            }
+
+            *(char_count
+                .entry((*el as char).to_ascii_lowercase())
+                .or_insert(0.0)) += 1.0 / decoded_len;
+        }
+
+        let current_score: f64 = score_plaintext(&mut char_count);
+
+        if current_score > score {
+            key = x;
+            score = current_score;
+            plaintext = String::from_utf8(decoded)?;
+        }
+    }
+
+    Ok((plaintext, score, key))
+}
+
+pub fn detect_single_byte_xor(data: Vec<&str>) -> String {
+    let mut score: f64 = 0.0;
+    let mut plaintext = String::from("");
+
+    for encoded in data {
+        if let Ok(decoded) = hex::decode(encoded) {
+            match single_byte_xor_cipher(decoded) {
+                Ok((temp_plaintext, temp_score, _)) => {
+                    if temp_score > score {
+                        score = temp_score;
+                        plaintext = temp_plaintext;
+                    }
+                }
+                Err(_) => continue,
+            };
+        }
+    }
+
+    plaintext
+}
+
+// Transposes `data` into `key_size` columns and breaks each one as single-byte XOR, returning
+// the key along with the summed column scores.
+pub fn solve_repeating_key(data: &[u8], key_size: usize) -> Result<(Vec<u8>, f64)> {
+    if key_size == 0 {
+        return Err(Error::InvalidKeySize(key_size));
+    }
+
+    let mut single_byte_blocks: Vec<Vec<u8>> = (0..key_size).map(|_| vec![]).collect();
+
+    for block in data.chunks(key_size) {
+        for (i, el) in block.iter().enumerate() {
+            single_byte_blocks[i].push(*el);
+        }
+    }
+
+    let mut key = vec![];
+    let mut score = 0f64;
+
+    for block in single_byte_blocks {
+        let (_, block_score, key_component) = single_byte_xor_cipher(block)?;
+        score += block_score;
+        key.push(key_component);
+    }
+
+    Ok((key, score))
+}
+
+pub fn break_repeating_key_xor(data: Vec<u8>) -> Result<(String, String)> {
+    let mut distances: Vec<(usize, f64)> = vec![];
+    for x in 2..=40 {
+        let mut total_distance = 0f64;
+        let blocks = data.chunks_exact(x).take(4).collect::<Vec<_>>();
+
+        if blocks.len() < 2 {
+            continue;
+        }
+
+        for i in 0..(blocks.len() - 1) {
+            total_distance += hamming_distance(blocks[i], blocks[i + 1])? / x as f64
+        }
+
+        let distance = total_distance / blocks.len() as f64;
+
+        distances.push((x, distance));
+    }
+
+    distances.sort_unstable_by(|a, b| a.1.partial_cmp(&b.1).unwrap());
+
+    let mut total_score = 0f64;
+    let mut xor_key = String::from("");
+    let mut plaintext = String::from("");
+
+    for (key_size, _) in distances.iter().take(3) {
+        let (key, current_score) = solve_repeating_key(&data, *key_size)?;
+
+        let mut data = data.to_vec();
+
+        repeating_key_xor(&key, &mut data)?;
+
+        if current_score > total_score {
+            total_score = current_score;
+            xor_key = String::from_utf8(key)?;
+            plaintext = String::from_utf8(data)?;
+        }
+    }
+
+    Ok((xor_key, plaintext))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Error;
+
+    #[test]
+    fn solve_repeating_key_rejects_zero_key_size() {
+        assert!(matches!(
+            super::solve_repeating_key(b"ciphertext", 0),
+            Err(Error::InvalidKeySize(0))
+        ));
+    }
+}
@@ -0,0 +1,310 @@
+use crate::cipher::BlockCipher;
+use crate::Error;
+use crate::Result;
+use std::convert::TryInto;
+
+const BLOCK_SIZE: usize = 16;
+
+const RCON: [u8; 10] = [0x01, 0x02, 0x04, 0x08, 0x10, 0x20, 0x40, 0x80, 0x1b, 0x36];
+
+const SBOX: [u8; 256] = [
+    0x63, 0x7c, 0x77, 0x7b, 0xf2, 0x6b, 0x6f, 0xc5, 0x30, 0x01, 0x67, 0x2b, 0xfe, 0xd7, 0xab, 0x76,
+    0xca, 0x82, 0xc9, 0x7d, 0xfa, 0x59, 0x47, 0xf0, 0xad, 0xd4, 0xa2, 0xaf, 0x9c, 0xa4, 0x72, 0xc0,
+    0xb7, 0xfd, 0x93, 0x26, 0x36, 0x3f, 0xf7, 0xcc, 0x34, 0xa5, 0xe5, 0xf1, 0x71, 0xd8, 0x31, 0x15,
+    0x04, 0xc7, 0x23, 0xc3, 0x18, 0x96, 0x05, 0x9a, 0x07, 0x12, 0x80, 0xe2, 0xeb, 0x27, 0xb2, 0x75,
+    0x09, 0x83, 0x2c, 0x1a, 0x1b, 0x6e, 0x5a, 0xa0, 0x52, 0x3b, 0xd6, 0xb3, 0x29, 0xe3, 0x2f, 0x84,
+    0x53, 0xd1, 0x00, 0xed, 0x20, 0xfc, 0xb1, 0x5b, 0x6a, 0xcb, 0xbe, 0x39, 0x4a, 0x4c, 0x58, 0xcf,
+    0xd0, 0xef, 0xaa, 0xfb, 0x43, 0x4d, 0x33, 0x85, 0x45, 0xf9, 0x02, 0x7f, 0x50, 0x3c, 0x9f, 0xa8,
+    0x51, 0xa3, 0x40, 0x8f, 0x92, 0x9d, 0x38, 0xf5, 0xbc, 0xb6, 0xda, 0x21, 0x10, 0xff, 0xf3, 0xd2,
+    0xcd, 0x0c, 0x13, 0xec, 0x5f, 0x97, 0x44, 0x17, 0xc4, 0xa7, 0x7e, 0x3d, 0x64, 0x5d, 0x19, 0x73,
+    0x60, 0x81, 0x4f, 0xdc, 0x22, 0x2a, 0x90, 0x88, 0x46, 0xee, 0xb8, 0x14, 0xde, 0x5e, 0x0b, 0xdb,
+    0xe0, 0x32, 0x3a, 0x0a, 0x49, 0x06, 0x24, 0x5c, 0xc2, 0xd3, 0xac, 0x62, 0x91, 0x95, 0xe4, 0x79,
+    0xe7, 0xc8, 0x37, 0x6d, 0x8d, 0xd5, 0x4e, 0xa9, 0x6c, 0x56, 0xf4, 0xea, 0x65, 0x7a, 0xae, 0x08,
+    0xba, 0x78, 0x25, 0x2e, 0x1c, 0xa6, 0xb4, 0xc6, 0xe8, 0xdd, 0x74, 0x1f, 0x4b, 0xbd, 0x8b, 0x8a,
+    0x70, 0x3e, 0xb5, 0x66, 0x48, 0x03, 0xf6, 0x0e, 0x61, 0x35, 0x57, 0xb9, 0x86, 0xc1, 0x1d, 0x9e,
+    0xe1, 0xf8, 0x98, 0x11, 0x69, 0xd9, 0x8e, 0x94, 0x9b, 0x1e, 0x87, 0xe9, 0xce, 0x55, 0x28, 0xdf,
+    0x8c, 0xa1, 0x89, 0x0d, 0xbf, 0xe6, 0x42, 0x68, 0x41, 0x99, 0x2d, 0x0f, 0xb0, 0x54, 0xbb, 0x16,
+];
+
+const INV_SBOX: [u8; 256] = [
+    0x52, 0x09, 0x6a, 0xd5, 0x30, 0x36, 0xa5, 0x38, 0xbf, 0x40, 0xa3, 0x9e, 0x81, 0xf3, 0xd7, 0xfb,
+    0x7c, 0xe3, 0x39, 0x82, 0x9b, 0x2f, 0xff, 0x87, 0x34, 0x8e, 0x43, 0x44, 0xc4, 0xde, 0xe9, 0xcb,
+    0x54, 0x7b, 0x94, 0x32, 0xa6, 0xc2, 0x23, 0x3d, 0xee, 0x4c, 0x95, 0x0b, 0x42, 0xfa, 0xc3, 0x4e,
+    0x08, 0x2e, 0xa1, 0x66, 0x28, 0xd9, 0x24, 0xb2, 0x76, 0x5b, 0xa2, 0x49, 0x6d, 0x8b, 0xd1, 0x25,
+    0x72, 0xf8, 0xf6, 0x64, 0x86, 0x68, 0x98, 0x16, 0xd4, 0xa4, 0x5c, 0xcc, 0x5d, 0x65, 0xb6, 0x92,
+    0x6c, 0x70, 0x48, 0x50, 0xfd, 0xed, 0xb9, 0xda, 0x5e, 0x15, 0x46, 0x57, 0xa7, 0x8d, 0x9d, 0x84,
+    0x90, 0xd8, 0xab, 0x00, 0x8c, 0xbc, 0xd3, 0x0a, 0xf7, 0xe4, 0x58, 0x05, 0xb8, 0xb3, 0x45, 0x06,
+    0xd0, 0x2c, 0x1e, 0x8f, 0xca, 0x3f, 0x0f, 0x02, 0xc1, 0xaf, 0xbd, 0x03, 0x01, 0x13, 0x8a, 0x6b,
+    0x3a, 0x91, 0x11, 0x41, 0x4f, 0x67, 0xdc, 0xea, 0x97, 0xf2, 0xcf, 0xce, 0xf0, 0xb4, 0xe6, 0x73,
+    0x96, 0xac, 0x74, 0x22, 0xe7, 0xad, 0x35, 0x85, 0xe2, 0xf9, 0x37, 0xe8, 0x1c, 0x75, 0xdf, 0x6e,
+    0x47, 0xf1, 0x1a, 0x71, 0x1d, 0x29, 0xc5, 0x89, 0x6f, 0xb7, 0x62, 0x0e, 0xaa, 0x18, 0xbe, 0x1b,
+    0xfc, 0x56, 0x3e, 0x4b, 0xc6, 0xd2, 0x79, 0x20, 0x9a, 0xdb, 0xc0, 0xfe, 0x78, 0xcd, 0x5a, 0xf4,
+    0x1f, 0xdd, 0xa8, 0x33, 0x88, 0x07, 0xc7, 0x31, 0xb1, 0x12, 0x10, 0x59, 0x27, 0x80, 0xec, 0x5f,
+    0x60, 0x51, 0x7f, 0xa9, 0x19, 0xb5, 0x4a, 0x0d, 0x2d, 0xe5, 0x7a, 0x9f, 0x93, 0xc9, 0x9c, 0xef,
+    0xa0, 0xe0, 0x3b, 0x4d, 0xae, 0x2a, 0xf5, 0xb0, 0xc8, 0xeb, 0xbb, 0x3c, 0x83, 0x53, 0x99, 0x61,
+    0x17, 0x2b, 0x04, 0x7e, 0xba, 0x77, 0xd6, 0x26, 0xe1, 0x69, 0x14, 0x63, 0x55, 0x21, 0x0c, 0x7d,
+];
+
+pub struct Aes {
+    round_keys: Vec<[u8; BLOCK_SIZE]>,
+}
+
+impl Aes {
+    pub const BLOCK_SIZE: usize = BLOCK_SIZE;
+
+    pub fn new(key: &[u8]) -> Result<Aes> {
+        let rounds = match key.len() {
+            16 => 10,
+            24 => 12,
+            32 => 14,
+            len => return Err(Error::InvalidKeySize(len)),
+        };
+
+        let key_words = key.len() / 4;
+        let total_words = 4 * (rounds + 1);
+        let mut words: Vec<[u8; 4]> = key
+            .chunks_exact(4)
+            .map(|word| [word[0], word[1], word[2], word[3]])
+            .collect();
+
+        for i in key_words..total_words {
+            let mut temp = words[i - 1];
+
+            if i % key_words == 0 {
+                temp.rotate_left(1);
+                sub_word(&mut temp);
+                temp[0] ^= RCON[i / key_words - 1];
+            } else if key_words > 6 && i % key_words == 4 {
+                sub_word(&mut temp);
+            }
+
+            let previous = words[i - key_words];
+            words.push([
+                previous[0] ^ temp[0],
+                previous[1] ^ temp[1],
+                previous[2] ^ temp[2],
+                previous[3] ^ temp[3],
+            ]);
+        }
+
+        let round_keys = words
+            .chunks_exact(4)
+            .map(|round| {
+                let mut round_key = [0u8; BLOCK_SIZE];
+                for (i, word) in round.iter().enumerate() {
+                    round_key[4 * i..4 * i + 4].copy_from_slice(word);
+                }
+                round_key
+            })
+            .collect();
+
+        Ok(Aes { round_keys })
+    }
+
+    pub fn rounds(&self) -> usize {
+        self.round_keys.len() - 1
+    }
+}
+
+impl BlockCipher for Aes {
+    fn block_size(&self) -> usize {
+        BLOCK_SIZE
+    }
+
+    fn encrypt_block(&self, block: &mut [u8]) -> Result<()> {
+        let state = as_state(block)?;
+        let rounds = self.rounds();
+
+        add_round_key(state, &self.round_keys[0]);
+
+        for round in 1..rounds {
+            sub_bytes(state, &SBOX);
+            shift_rows(state);
+            mix_columns(state);
+            add_round_key(state, &self.round_keys[round]);
+        }
+
+        sub_bytes(state, &SBOX);
+        shift_rows(state);
+        add_round_key(state, &self.round_keys[rounds]);
+
+        Ok(())
+    }
+
+    fn decrypt_block(&self, block: &mut [u8]) -> Result<()> {
+        let state = as_state(block)?;
+        let rounds = self.rounds();
+
+        add_round_key(state, &self.round_keys[rounds]);
+
+        for round in (1..rounds).rev() {
+            inv_shift_rows(state);
+            sub_bytes(state, &INV_SBOX);
+            add_round_key(state, &self.round_keys[round]);
+            inv_mix_columns(state);
+        }
+
+        inv_shift_rows(state);
+        sub_bytes(state, &INV_SBOX);
+        add_round_key(state, &self.round_keys[0]);
+
+        Ok(())
+    }
+}
+
+fn as_state(block: &mut [u8]) -> Result<&mut [u8; BLOCK_SIZE]> {
+    let len = block.len();
+    block.try_into().map_err(|_| Error::LengthMismatch {
+        expected: BLOCK_SIZE,
+        actual: len,
+    })
+}
+
+fn sub_word(word: &mut [u8; 4]) {
+    for byte in word.iter_mut() {
+        *byte = SBOX[*byte as usize];
+    }
+}
+
+// The state is stored column-major, so byte (row, column) lives at index row + 4 * column.
+fn sub_bytes(state: &mut [u8; BLOCK_SIZE], table: &[u8; 256]) {
+    for byte in state.iter_mut() {
+        *byte = table[*byte as usize];
+    }
+}
+
+fn shift_rows(state: &mut [u8; BLOCK_SIZE]) {
+    let copy = *state;
+    for row in 1..4 {
+        for column in 0..4 {
+            state[row + 4 * column] = copy[row + 4 * ((column + row) % 4)];
+        }
+    }
+}
+
+fn inv_shift_rows(state: &mut [u8; BLOCK_SIZE]) {
+    let copy = *state;
+    for row in 1..4 {
+        for column in 0..4 {
+            state[row + 4 * ((column + row) % 4)] = copy[row + 4 * column];
+        }
+    }
+}
+
+fn xtime(x: u8) -> u8 {
+    (x << 1) ^ if x & 0x80 != 0 { 0x1b } else { 0 }
+}
+
+fn gmul(mut a: u8, mut b: u8) -> u8 {
+    let mut product = 0;
+    while b != 0 {
+        if b & 1 != 0 {
+            product ^= a;
+        }
+        a = xtime(a);
+        b >>= 1;
+    }
+    product
+}
+
+fn mix_columns(state: &mut [u8; BLOCK_SIZE]) {
+    for column in state.chunks_exact_mut(4) {
+        let [a, b, c, d] = [column[0], column[1], column[2], column[3]];
+        column[0] = xtime(a) ^ xtime(b) ^ b ^ c ^ d;
+        column[1] = a ^ xtime(b) ^ xtime(c) ^ c ^ d;
+        column[2] = a ^ b ^ xtime(c) ^ xtime(d) ^ d;
+        column[3] = xtime(a) ^ a ^ b ^ c ^ xtime(d);
+    }
+}
+
+fn inv_mix_columns(state: &mut [u8; BLOCK_SIZE]) {
+    for column in state.chunks_exact_mut(4) {
+        let [a, b, c, d] = [column[0], column[1], column[2], column[3]];
+        column[0] = gmul(a, 14) ^ gmul(b, 11) ^ gmul(c, 13) ^ gmul(d, 9);
+        column[1] = gmul(a, 9) ^ gmul(b, 14) ^ gmul(c, 11) ^ gmul(d, 13);
+        column[2] = gmul(a, 13) ^ gmul(b, 9) ^ gmul(c, 14) ^ gmul(d, 11);
+        column[3] = gmul(a, 11) ^ gmul(b, 13) ^ gmul(c, 9) ^ gmul(d, 14);
+    }
+}
+
+fn add_round_key(state: &mut [u8; BLOCK_SIZE], round_key: &[u8; BLOCK_SIZE]) {
+    for (byte, key) in state.iter_mut().zip(round_key) {
+        *byte ^= key;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Aes;
+    use crate::cipher::BlockCipher;
+
+    // FIPS-197, Appendix C.
+    const PLAINTEXT: &str = "00112233445566778899aabbccddeeff";
+
+    fn check_vector(key: &str, ciphertext: &str) {
+        let aes = Aes::new(&hex::decode(key).unwrap()).unwrap();
+        let mut block = hex::decode(PLAINTEXT).unwrap();
+
+        aes.encrypt_block(&mut block).unwrap();
+        assert_eq!(hex::encode(&block), ciphertext);
+
+        aes.decrypt_block(&mut block).unwrap();
+        assert_eq!(hex::encode(&block), PLAINTEXT);
+    }
+
+    #[test]
+    fn fips_197_aes_128() {
+        check_vector(
+            "000102030405060708090a0b0c0d0e0f",
+            "69c4e0d86a7b0430d8cdb78070b4c55a",
+        );
+    }
+
+    #[test]
+    fn fips_197_aes_192() {
+        check_vector(
+            "000102030405060708090a0b0c0d0e0f1011121314151617",
+            "dda97ca4864cdfe06eaf70a0ec0d7191",
+        );
+    }
+
+    #[test]
+    fn fips_197_aes_256() {
+        check_vector(
+            "000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f",
+            "8ea2b7ca516745bfeafc49904b496089",
+        );
+    }
+
+    #[test]
+    fn invalid_key_size() {
+        assert!(Aes::new(&[0u8; 20]).is_err());
+    }
+
+    #[cfg(feature = "openssl")]
+    #[test]
+    fn matches_openssl() {
+        use openssl::symm::{encrypt, Cipher};
+
+        let ciphers = [
+            (16, Cipher::aes_128_ecb()),
+            (24, Cipher::aes_192_ecb()),
+            (32, Cipher::aes_256_ecb()),
+        ];
+
+        for (key_len, cipher) in ciphers.iter() {
+            let key = (0..*key_len).map(|i| (i * 7 + 3) as u8).collect::<Vec<_>>();
+            let aes = Aes::new(&key).unwrap();
+
+            for seed in 0..32u8 {
+                let mut block = (0..16u8)
+                    .map(|i| i.wrapping_mul(31).wrapping_add(seed))
+                    .collect::<Vec<_>>();
+                let expected = encrypt(*cipher, &key, None, &block).unwrap();
+
+                aes.encrypt_block(&mut block).unwrap();
+                assert_eq!(&block[..], &expected[..16]);
+            }
+        }
+    }
+}
@@ -0,0 +1,15 @@
+pub mod aes;
+pub mod mt19937_stream;
+
+pub use aes::Aes;
+pub use mt19937_stream::mt19937_stream_cipher;
+
+use crate::Result;
+
+pub trait BlockCipher {
+    fn block_size(&self) -> usize;
+
+    fn encrypt_block(&self, block: &mut [u8]) -> Result<()>;
+
+    fn decrypt_block(&self, block: &mut [u8]) -> Result<()>;
+}
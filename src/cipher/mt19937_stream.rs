@@ -0,0 +1,14 @@
+use crate::rng::Mt19937;
+
+// One keystream byte per generator output: the low 8 bits.
+pub fn mt19937_keystream(seed: u32, len: usize) -> Vec<u8> {
+    let mut mt = Mt19937::new(seed);
+    (0..len).map(|_| mt.next_u32() as u8).collect()
+}
+
+pub fn mt19937_stream_cipher(seed: u16, data: &mut [u8]) {
+    let keystream = mt19937_keystream(seed as u32, data.len());
+    for (el, key) in data.iter_mut().zip(keystream) {
+        *el ^= key;
+    }
+}
@@ -0,0 +1,61 @@
+use std::cell::Cell;
+use std::thread;
+use std::time::Duration;
+use std::time::SystemTime;
+use std::time::UNIX_EPOCH;
+
+pub trait Clock {
+    // Time elapsed since the Unix epoch.
+    fn now(&self) -> Duration;
+
+    fn sleep(&self, duration: Duration);
+
+    fn unix_timestamp(&self) -> u64 {
+        self.now().as_secs()
+    }
+}
+
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> Duration {
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+    }
+
+    fn sleep(&self, duration: Duration) {
+        thread::sleep(duration)
+    }
+}
+
+// A clock that only moves when told to, so tests can "wait" without sleeping.
+pub struct SimulatedClock {
+    now: Cell<Duration>,
+}
+
+impl SimulatedClock {
+    pub fn new(now: Duration) -> SimulatedClock {
+        SimulatedClock {
+            now: Cell::new(now),
+        }
+    }
+
+    pub fn at_unix_timestamp(secs: u64) -> SimulatedClock {
+        Self::new(Duration::from_secs(secs))
+    }
+
+    pub fn advance(&self, duration: Duration) {
+        self.now.set(self.now.get() + duration);
+    }
+}
+
+impl Clock for SimulatedClock {
+    fn now(&self) -> Duration {
+        self.now.get()
+    }
+
+    fn sleep(&self, duration: Duration) {
+        self.advance(duration)
+    }
+}
@@ -0,0 +1,182 @@
+mod groups;
+
+use crate::hash::Sha1;
+use crate::Error;
+use crate::Result;
+use num_bigint::BigUint;
+use num_bigint::RandBigInt;
+
+// Left-to-right square-and-multiply. The modulus must be non-zero, which every `Group`
+// guarantees, so this stays inside the crate rather than checking on every call.
+pub(crate) fn modexp(base: &BigUint, exponent: &BigUint, modulus: &BigUint) -> BigUint {
+    let one = BigUint::from(1u32);
+    if *modulus == one {
+        return BigUint::from(0u32);
+    }
+
+    let base = base % modulus;
+    let mut result = one;
+
+    for bit in (0..exponent.bits()).rev() {
+        result = &result * &result % modulus;
+        if exponent.bit(bit) {
+            result = result * &base % modulus;
+        }
+    }
+
+    result
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Group {
+    pub(crate) p: BigUint,
+    pub(crate) g: BigUint,
+}
+
+impl Group {
+    pub fn new(p: BigUint, g: BigUint) -> Result<Group> {
+        if p < BigUint::from(5u32) {
+            return Err(Error::InvalidGroup("modulus is too small"));
+        }
+
+        if g < BigUint::from(2u32) || g >= &p - 1u32 {
+            return Err(Error::InvalidGroup("generator must lie in [2, p - 2]"));
+        }
+
+        Ok(Group { p, g })
+    }
+
+    pub fn p(&self) -> &BigUint {
+        &self.p
+    }
+
+    pub fn g(&self) -> &BigUint {
+        &self.g
+    }
+
+    // The toy group from the first half of challenge 33.
+    pub fn cryptopals_small() -> Group {
+        Group {
+            p: BigUint::from(37u32),
+            g: BigUint::from(5u32),
+        }
+    }
+
+    // What the challenges call the "NIST" group is the 1536-bit MODP group.
+    pub fn cryptopals_nist() -> Group {
+        Group::modp(1536).unwrap()
+    }
+
+    pub fn modp(bits: usize) -> Result<Group> {
+        let hex = match bits {
+            1536 => groups::MODP_1536,
+            2048 => groups::MODP_2048,
+            3072 => groups::MODP_3072,
+            4096 => groups::MODP_4096,
+            6144 => groups::MODP_6144,
+            8192 => groups::MODP_8192,
+            _ => return Err(Error::InvalidGroup("no MODP group of that size")),
+        };
+
+        Ok(Group {
+            p: BigUint::parse_bytes(hex.as_bytes(), 16).unwrap(),
+            g: BigUint::from(2u32),
+        })
+    }
+}
+
+pub struct DiffieHellman {
+    group: Group,
+    private_key: BigUint,
+    public_key: BigUint,
+}
+
+impl DiffieHellman {
+    pub fn new(group: Group) -> DiffieHellman {
+        let private_key = rand::thread_rng().gen_biguint_range(&BigUint::from(1u32), &group.p);
+        DiffieHellman::with_private_key(group, private_key)
+    }
+
+    pub fn with_private_key(group: Group, private_key: BigUint) -> DiffieHellman {
+        let public_key = modexp(&group.g, &private_key, &group.p);
+
+        DiffieHellman {
+            group,
+            private_key,
+            public_key,
+        }
+    }
+
+    pub fn group(&self) -> &Group {
+        &self.group
+    }
+
+    pub fn public_key(&self) -> &BigUint {
+        &self.public_key
+    }
+
+    // The peer's key is deliberately not validated, which is what lets a man in the middle
+    // substitute degenerate values like p.
+    pub fn shared_secret(&self, peer_public_key: &BigUint) -> BigUint {
+        modexp(peer_public_key, &self.private_key, &self.group.p)
+    }
+
+    pub fn session_key(&self, peer_public_key: &BigUint) -> Vec<u8> {
+        session_key(&self.shared_secret(peer_public_key))
+    }
+}
+
+// AES-128 key material: the first 16 bytes of SHA1 over the big-endian shared secret.
+pub fn session_key(shared_secret: &BigUint) -> Vec<u8> {
+    Sha1::digest(&shared_secret.to_bytes_be())[..16].to_vec()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::modexp;
+    use super::Group;
+    use num_bigint::BigUint;
+    use num_bigint::RandBigInt;
+
+    #[test]
+    fn modexp_matches_modpow() {
+        let mut rng = rand::thread_rng();
+        let p = Group::modp(2048).unwrap().p;
+
+        for _ in 0..8 {
+            let base = rng.gen_biguint(2048);
+            let exponent = rng.gen_biguint(512);
+            assert_eq!(modexp(&base, &exponent, &p), base.modpow(&exponent, &p));
+        }
+
+        assert_eq!(
+            modexp(
+                &BigUint::from(4u32),
+                &BigUint::from(13u32),
+                &BigUint::from(497u32)
+            ),
+            BigUint::from(445u32)
+        );
+        assert_eq!(
+            modexp(
+                &BigUint::from(7u32),
+                &BigUint::from(0u32),
+                &BigUint::from(13u32)
+            ),
+            BigUint::from(1u32)
+        );
+    }
+
+    #[test]
+    fn modp_groups() {
+        for bits in [1536, 2048, 3072, 4096, 6144, 8192].iter() {
+            let group = Group::modp(*bits).unwrap();
+            assert_eq!(group.p.bits(), *bits as u64);
+            assert_eq!(group.g, BigUint::from(2u32));
+        }
+
+        assert!(Group::modp(1024).is_err());
+        assert!(Group::new(BigUint::from(23u32), BigUint::from(22u32)).is_err());
+        assert!(Group::new(BigUint::from(23u32), BigUint::from(5u32)).is_ok());
+    }
+}
@@ -0,0 +1,200 @@
+use crate::Error;
+use crate::Result;
+
+pub fn convert_hex_to_base64(data: &str) -> Result<String> {
+    let decoded = hex::decode(data)?;
+    Ok(base64::encode(&decoded))
+}
+
+// Metacharacters are percent-encoded, so a value can never smuggle in another key.
+fn escape_kv(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '%' => escaped.push_str("%25"),
+            '&' => escaped.push_str("%26"),
+            '=' => escaped.push_str("%3D"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+fn unescape_kv(value: &str) -> Result<String> {
+    let mut unescaped = String::with_capacity(value.len());
+    let mut rest = value;
+
+    while let Some(index) = rest.find('%') {
+        unescaped.push_str(&rest[..index]);
+
+        let code = rest
+            .get(index + 1..index + 3)
+            .ok_or(Error::Parse("truncated escape sequence"))?;
+        match code {
+            "25" => unescaped.push('%'),
+            "26" => unescaped.push('&'),
+            "3D" | "3d" => unescaped.push('='),
+            _ => return Err(Error::Parse("unknown escape sequence")),
+        }
+
+        rest = &rest[index + 3..];
+    }
+
+    unescaped.push_str(rest);
+    Ok(unescaped)
+}
+
+pub fn encode_kv(pairs: &[(&str, &str)]) -> String {
+    pairs
+        .iter()
+        .map(|(key, value)| format!("{}={}", escape_kv(key), escape_kv(value)))
+        .collect::<Vec<_>>()
+        .join("&")
+}
+
+pub fn parse_kv(data: &str) -> Result<Vec<(String, String)>> {
+    if data.is_empty() {
+        return Ok(vec![]);
+    }
+
+    data.split('&')
+        .map(|pair| {
+            // Splitting on '&' already rules it out, so only a stray '=' can remain unescaped.
+            let mut parts = pair.split('=');
+            let key = parts.next().unwrap_or("");
+            let value = parts.next().ok_or(Error::Parse("missing '=' in pair"))?;
+            if parts.next().is_some() {
+                return Err(Error::Parse("unescaped '=' in pair"));
+            }
+
+            Ok((unescape_kv(key)?, unescape_kv(value)?))
+        })
+        .collect()
+}
+
+// RFC 3986 percent-encoding: everything but the unreserved characters becomes %XX.
+pub fn percent_encode(data: &[u8]) -> String {
+    let mut encoded = String::with_capacity(data.len());
+    for byte in data {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {
+                encoded.push(*byte as char)
+            }
+            byte => encoded.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    encoded
+}
+
+// Undoes any %XX escape, and treats '+' as a space the way HTML forms send it.
+pub fn percent_decode(data: &str) -> Result<Vec<u8>> {
+    let bytes = data.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut index = 0;
+
+    while index < bytes.len() {
+        match bytes[index] {
+            b'%' => {
+                let code = bytes
+                    .get(index + 1..index + 3)
+                    .ok_or(Error::Parse("truncated escape sequence"))?;
+                decoded.push(hex::decode(code)?[0]);
+                index += 3;
+            }
+            b'+' => {
+                decoded.push(b' ');
+                index += 1;
+            }
+            byte => {
+                decoded.push(byte);
+                index += 1;
+            }
+        }
+    }
+
+    Ok(decoded)
+}
+
+pub fn encode_query(pairs: &[(&str, &str)]) -> String {
+    pairs
+        .iter()
+        .map(|(key, value)| {
+            format!(
+                "{}={}",
+                percent_encode(key.as_bytes()),
+                percent_encode(value.as_bytes())
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("&")
+}
+
+// Unlike `parse_kv`, a parameter without '=' is accepted as having an empty value.
+pub fn parse_query(query: &str) -> Result<Vec<(String, String)>> {
+    query
+        .split('&')
+        .filter(|pair| !pair.is_empty())
+        .map(|pair| {
+            let mut parts = pair.splitn(2, '=');
+            let key = percent_decode(parts.next().unwrap_or(""))?;
+            let value = percent_decode(parts.next().unwrap_or(""))?;
+
+            Ok((String::from_utf8(key)?, String::from_utf8(value)?))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn parse_kv() {
+        assert_eq!(
+            super::parse_kv("foo=bar&baz=qux&zap=zazzle").unwrap(),
+            vec![
+                ("foo".to_string(), "bar".to_string()),
+                ("baz".to_string(), "qux".to_string()),
+                ("zap".to_string(), "zazzle".to_string()),
+            ]
+        );
+        assert!(super::parse_kv("foo=bar&baz").is_err());
+        assert!(super::parse_kv("a=b=c").is_err());
+        assert!(super::parse_kv("foo=bar&&baz=qux").is_err());
+    }
+
+    #[test]
+    fn encode_kv_escapes_metacharacters() {
+        let encoded = super::encode_kv(&[("email", "foo@bar.com&role=admin"), ("uid", "10")]);
+
+        assert_eq!(encoded, "email=foo@bar.com%26role%3Dadmin&uid=10");
+        assert_eq!(
+            super::parse_kv(&encoded).unwrap(),
+            vec![
+                ("email".to_string(), "foo@bar.com&role=admin".to_string()),
+                ("uid".to_string(), "10".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn query_round_trip() {
+        let encoded = super::encode_query(&[("file", "a/b c&d=é"), ("signature", "00ff")]);
+
+        assert_eq!(encoded, "file=a%2Fb%20c%26d%3D%C3%A9&signature=00ff");
+        assert_eq!(
+            super::parse_query(&encoded).unwrap(),
+            vec![
+                ("file".to_string(), "a/b c&d=é".to_string()),
+                ("signature".to_string(), "00ff".to_string()),
+            ]
+        );
+        assert_eq!(
+            super::parse_query("file=a%2fb+c&flag").unwrap(),
+            vec![
+                ("file".to_string(), "a/b c".to_string()),
+                ("flag".to_string(), "".to_string()),
+            ]
+        );
+        assert!(super::parse_query("file=%2").is_err());
+        assert!(super::parse_query("file=%zz").is_err());
+    }
+}
@@ -0,0 +1,117 @@
+use base64::DecodeError;
+use hex::FromHexError;
+#[cfg(feature = "openssl")]
+use openssl::error::ErrorStack;
+use std::fmt;
+use std::io;
+use std::string::FromUtf8Error;
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+#[derive(Debug)]
+pub enum Error {
+    LengthMismatch {
+        expected: usize,
+        actual: usize,
+    },
+    NotBlockAligned {
+        len: usize,
+        block_size: usize,
+    },
+    BadPadding,
+    InvalidBlockSize(usize),
+    InvalidKeySize(usize),
+    InvalidIvSize(usize),
+    InvalidCounter(u64),
+    InvalidOffset(u64),
+    InvalidGroup(&'static str),
+    Parse(&'static str),
+    NonAsciiPlaintext(Vec<u8>),
+    AttackFailed(&'static str),
+    Protocol(&'static str),
+    Hex(FromHexError),
+    Base64(DecodeError),
+    Utf8(FromUtf8Error),
+    Io(io::Error),
+    #[cfg(feature = "openssl")]
+    Backend(ErrorStack),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::LengthMismatch { expected, actual } => {
+                write!(f, "length mismatch: expected {}, got {}", expected, actual)
+            }
+            Error::NotBlockAligned { len, block_size } => write!(
+                f,
+                "length {} is not a multiple of the block size {}",
+                len, block_size
+            ),
+            Error::BadPadding => write!(f, "bad padding"),
+            Error::InvalidBlockSize(size) => write!(f, "invalid block size: {}", size),
+            Error::InvalidKeySize(size) => write!(f, "invalid key size: {}", size),
+            Error::InvalidIvSize(size) => write!(f, "invalid IV size: {}", size),
+            Error::InvalidCounter(counter) => write!(f, "counter out of range: {}", counter),
+            Error::InvalidOffset(offset) => write!(f, "offset out of range: {}", offset),
+            Error::InvalidGroup(reason) => write!(f, "invalid group: {}", reason),
+            Error::NonAsciiPlaintext(plaintext) => {
+                write!(f, "plaintext is not ASCII: {}", hex::encode(plaintext))
+            }
+            Error::Parse(reason) => write!(f, "parse error: {}", reason),
+            Error::AttackFailed(reason) => write!(f, "attack failed: {}", reason),
+            Error::Protocol(reason) => write!(f, "protocol error: {}", reason),
+            Error::Hex(err) => write!(f, "hex decoding error: {}", err),
+            Error::Base64(err) => write!(f, "base64 decoding error: {}", err),
+            Error::Utf8(err) => write!(f, "UTF-8 decoding error: {}", err),
+            Error::Io(err) => write!(f, "I/O error: {}", err),
+            #[cfg(feature = "openssl")]
+            Error::Backend(err) => write!(f, "backend error: {}", err),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Hex(err) => Some(err),
+            Error::Base64(err) => Some(err),
+            Error::Utf8(err) => Some(err),
+            Error::Io(err) => Some(err),
+            #[cfg(feature = "openssl")]
+            Error::Backend(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<FromHexError> for Error {
+    fn from(err: FromHexError) -> Self {
+        Error::Hex(err)
+    }
+}
+
+impl From<DecodeError> for Error {
+    fn from(err: DecodeError) -> Self {
+        Error::Base64(err)
+    }
+}
+
+impl From<FromUtf8Error> for Error {
+    fn from(err: FromUtf8Error) -> Self {
+        Error::Utf8(err)
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(err: io::Error) -> Self {
+        Error::Io(err)
+    }
+}
+
+#[cfg(feature = "openssl")]
+impl From<ErrorStack> for Error {
+    fn from(err: ErrorStack) -> Self {
+        Error::Backend(err)
+    }
+}
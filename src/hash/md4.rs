@@ -0,0 +1,191 @@
+use crate::hash::merkle_damgard;
+use crate::hash::merkle_damgard::md_padding;
+use crate::hash::merkle_damgard::BlockBuffer;
+use crate::hash::merkle_damgard::ByteOrder;
+use crate::Result;
+
+const ORDER: ByteOrder = ByteOrder::LittleEndian;
+
+const INITIAL_STATE: [u32; 4] = [0x6745_2301, 0xefcd_ab89, 0x98ba_dcfe, 0x1032_5476];
+
+#[derive(Clone)]
+pub struct Md4 {
+    state: [u32; 4],
+    blocks: BlockBuffer,
+}
+
+impl Md4 {
+    pub const BLOCK_SIZE: usize = merkle_damgard::BLOCK_SIZE;
+    pub const OUTPUT_SIZE: usize = 16;
+
+    pub fn new() -> Md4 {
+        Md4 {
+            state: INITIAL_STATE,
+            blocks: BlockBuffer::new(),
+        }
+    }
+
+    pub fn from_state(state: [u32; 4], processed_len: u64) -> Result<Md4> {
+        Ok(Md4 {
+            state,
+            blocks: BlockBuffer::resume(processed_len)?,
+        })
+    }
+
+    pub fn from_digest(digest: &[u8; 16], processed_len: u64) -> Result<Md4> {
+        let mut state = [0u32; 4];
+        ORDER.read_words(digest, &mut state);
+        Md4::from_state(state, processed_len)
+    }
+
+    pub fn state(&self) -> [u32; 4] {
+        self.state
+    }
+
+    pub fn update(&mut self, data: &[u8]) {
+        let state = &mut self.state;
+        self.blocks.update(data, |block| compress(state, block));
+    }
+
+    pub fn finalize(mut self) -> [u8; 16] {
+        let padding = md_padding(self.blocks.length(), ORDER);
+        self.update(&padding);
+
+        let mut digest = [0u8; 16];
+        ORDER.write_words(&self.state, &mut digest);
+        digest
+    }
+
+    pub fn digest(data: &[u8]) -> [u8; 16] {
+        let mut md4 = Md4::new();
+        md4.update(data);
+        md4.finalize()
+    }
+}
+
+impl Default for Md4 {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// RFC 1320, section 3.4. Each round applies its own boolean function and constant to the four
+// words in turn, visiting the message words in the order given by `schedule`.
+fn compress(state: &mut [u32; 4], block: &[u8]) {
+    let mut x = [0u32; 16];
+    ORDER.read_words(block, &mut x);
+
+    let f = |x: u32, y: u32, z: u32| (x & y) | (!x & z);
+    let g = |x: u32, y: u32, z: u32| (x & y) | (x & z) | (y & z);
+    let h = |x: u32, y: u32, z: u32| x ^ y ^ z;
+
+    type Round = (fn(u32, u32, u32) -> u32, u32, [usize; 16], [u32; 4]);
+    let rounds: [Round; 3] = [
+        (
+            f,
+            0,
+            [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15],
+            [3, 7, 11, 19],
+        ),
+        (
+            g,
+            0x5a82_7999,
+            [0, 4, 8, 12, 1, 5, 9, 13, 2, 6, 10, 14, 3, 7, 11, 15],
+            [3, 5, 9, 13],
+        ),
+        (
+            h,
+            0x6ed9_eba1,
+            [0, 8, 4, 12, 2, 10, 6, 14, 1, 9, 5, 13, 3, 11, 7, 15],
+            [3, 9, 11, 15],
+        ),
+    ];
+
+    let mut words = *state;
+    for (function, constant, schedule, shifts) in rounds.iter() {
+        for (step, index) in schedule.iter().enumerate() {
+            // Steps rotate through a, d, c, b; the other three are the function's inputs.
+            let target = (4 - step % 4) % 4;
+            let (b, c, d) = (
+                words[(target + 1) % 4],
+                words[(target + 2) % 4],
+                words[(target + 3) % 4],
+            );
+
+            words[target] = words[target]
+                .wrapping_add(function(b, c, d))
+                .wrapping_add(x[*index])
+                .wrapping_add(*constant)
+                .rotate_left(shifts[step % 4]);
+        }
+    }
+
+    for (word, value) in state.iter_mut().zip(words.iter()) {
+        *word = word.wrapping_add(*value);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Md4;
+    use crate::hash::merkle_damgard::md_padding;
+    use crate::hash::merkle_damgard::ByteOrder;
+
+    // RFC 1320, Appendix A.5.
+    #[test]
+    fn rfc_vectors() {
+        let vectors: [(&[u8], &str); 7] = [
+            (b"", "31d6cfe0d16ae931b73c59d7e0c089c0"),
+            (b"a", "bde52cb31de33e46245e05fbdbd6fb24"),
+            (b"abc", "a448017aaf21d8525fc10ae87aa6729d"),
+            (b"message digest", "d9130a8164549fe818874806e1c7014b"),
+            (
+                b"abcdefghijklmnopqrstuvwxyz",
+                "d79e1c308aa5bbcdeea8ed63df412da9",
+            ),
+            (
+                b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789",
+                "043f8582f241db351ce627e153e7f0e4",
+            ),
+            (
+                b"12345678901234567890123456789012345678901234567890123456789012345678901234567890",
+                "e33b4ddc9c38f2199c3e7b164fcc0536",
+            ),
+        ];
+
+        for (message, digest) in vectors.iter() {
+            assert_eq!(hex::encode(Md4::digest(message)), *digest);
+        }
+    }
+
+    #[test]
+    fn resume_from_state() {
+        let message = [0xa5u8; 150];
+
+        let mut prefix = Md4::new();
+        prefix.update(&message[..64]);
+        let mut resumed = Md4::from_state(prefix.state(), 64).unwrap();
+        for byte in message[64..].iter() {
+            resumed.update(&[*byte]);
+        }
+
+        assert_eq!(resumed.finalize(), Md4::digest(&message));
+        assert!(Md4::from_state(prefix.state(), 10).is_err());
+    }
+
+    // A digest is the state after the message and its padding, so hashing can carry on from
+    // there as if the padding had been part of the message.
+    #[test]
+    fn resume_from_digest() {
+        let message = b"a message that is hashed, then extended";
+        let mut padded = message.to_vec();
+        padded.extend(md_padding(message.len() as u64, ByteOrder::LittleEndian));
+
+        let mut resumed = Md4::from_digest(&Md4::digest(message), padded.len() as u64).unwrap();
+        resumed.update(b" with a suffix");
+        padded.extend_from_slice(b" with a suffix");
+
+        assert_eq!(resumed.finalize(), Md4::digest(&padded));
+        assert!(Md4::from_digest(&Md4::digest(message), 10).is_err());
+    }
+}
@@ -0,0 +1,132 @@
+use crate::Error;
+use crate::Result;
+
+pub const BLOCK_SIZE: usize = 64;
+
+// SHA-1 and MD4 differ only in which way round they read and write their 32-bit words and
+// the length field.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ByteOrder {
+    BigEndian,
+    LittleEndian,
+}
+
+impl ByteOrder {
+    pub(crate) fn read_words(self, bytes: &[u8], words: &mut [u32]) {
+        for (word, chunk) in words.iter_mut().zip(bytes.chunks_exact(4)) {
+            let chunk = [chunk[0], chunk[1], chunk[2], chunk[3]];
+            *word = match self {
+                ByteOrder::BigEndian => u32::from_be_bytes(chunk),
+                ByteOrder::LittleEndian => u32::from_le_bytes(chunk),
+            };
+        }
+    }
+
+    pub(crate) fn write_words(self, words: &[u32], bytes: &mut [u8]) {
+        for (chunk, word) in bytes.chunks_exact_mut(4).zip(words) {
+            chunk.copy_from_slice(&match self {
+                ByteOrder::BigEndian => word.to_be_bytes(),
+                ByteOrder::LittleEndian => word.to_le_bytes(),
+            });
+        }
+    }
+}
+
+// Merkle-Damgard strengthening: a 0x80 byte, zeros up to 56 mod 64, then the message length
+// in bits as a u64.
+pub fn md_padding(message_len: u64, order: ByteOrder) -> Vec<u8> {
+    let mut padding = vec![0x80];
+    let zeros = (119 - (message_len % BLOCK_SIZE as u64) as usize) % BLOCK_SIZE;
+    padding.extend(vec![0u8; zeros]);
+
+    let bits = message_len.wrapping_mul(8);
+    padding.extend_from_slice(&match order {
+        ByteOrder::BigEndian => bits.to_be_bytes(),
+        ByteOrder::LittleEndian => bits.to_le_bytes(),
+    });
+    padding
+}
+
+// Collects input into whole blocks for a compression function.
+#[derive(Clone)]
+pub(crate) struct BlockBuffer {
+    buffer: Vec<u8>,
+    // Bytes hashed so far, including any that are still sitting in the buffer.
+    length: u64,
+}
+
+impl BlockBuffer {
+    pub(crate) fn new() -> BlockBuffer {
+        BlockBuffer {
+            buffer: Vec::with_capacity(BLOCK_SIZE),
+            length: 0,
+        }
+    }
+
+    // Picks up after `processed_len` bytes, which must fall on a block boundary since no partial
+    // block can be recovered from a chaining state alone.
+    pub(crate) fn resume(processed_len: u64) -> Result<BlockBuffer> {
+        if !processed_len.is_multiple_of(BLOCK_SIZE as u64) {
+            return Err(Error::NotBlockAligned {
+                len: processed_len as usize,
+                block_size: BLOCK_SIZE,
+            });
+        }
+
+        Ok(BlockBuffer {
+            buffer: Vec::with_capacity(BLOCK_SIZE),
+            length: processed_len,
+        })
+    }
+
+    pub(crate) fn length(&self) -> u64 {
+        self.length
+    }
+
+    pub(crate) fn update<F: FnMut(&[u8])>(&mut self, mut data: &[u8], mut compress: F) {
+        self.length += data.len() as u64;
+
+        if !self.buffer.is_empty() {
+            let take = data.len().min(BLOCK_SIZE - self.buffer.len());
+            self.buffer.extend_from_slice(&data[..take]);
+            data = &data[take..];
+
+            if self.buffer.len() < BLOCK_SIZE {
+                return;
+            }
+
+            compress(&self.buffer);
+            self.buffer.clear();
+        }
+
+        let mut blocks = data.chunks_exact(BLOCK_SIZE);
+        for block in &mut blocks {
+            compress(block);
+        }
+        self.buffer.extend_from_slice(blocks.remainder());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::md_padding;
+    use super::ByteOrder;
+
+    #[test]
+    fn padding_reaches_block_boundary() {
+        for len in 0..200u64 {
+            let padding = md_padding(len, ByteOrder::BigEndian);
+            assert_eq!((len + padding.len() as u64) % 64, 0);
+            assert!(padding.len() >= 9);
+        }
+
+        assert_eq!(
+            &md_padding(3, ByteOrder::BigEndian)[53..],
+            &[0, 0, 0, 0, 0, 0, 0, 24]
+        );
+        assert_eq!(
+            &md_padding(3, ByteOrder::LittleEndian)[53..],
+            &[24, 0, 0, 0, 0, 0, 0, 0]
+        );
+    }
+}
@@ -0,0 +1,83 @@
+pub mod md4;
+pub mod merkle_damgard;
+pub mod sha1;
+
+pub use md4::Md4;
+pub use merkle_damgard::md_padding;
+pub use merkle_damgard::ByteOrder;
+pub use sha1::Sha1;
+
+use crate::Error;
+use crate::Result;
+use std::convert::TryInto;
+
+fn output_array<const LEN: usize>(digest: &[u8]) -> Result<[u8; LEN]> {
+    digest.try_into().map_err(|_| Error::LengthMismatch {
+        expected: LEN,
+        actual: digest.len(),
+    })
+}
+
+// Just enough of a common interface for constructions, like HMAC, that are generic over the hash.
+pub trait Hash: Default {
+    const BLOCK_SIZE: usize;
+    const OUTPUT_SIZE: usize;
+
+    fn update(&mut self, data: &[u8]);
+
+    fn finalize_to_vec(self) -> Vec<u8>;
+
+    // The padding appended to a message of `message_len` bytes before its final block.
+    fn padding(message_len: u64) -> Vec<u8>;
+
+    // Carries on from a digest as though it were the state after `processed_len` bytes.
+    fn resume(digest: &[u8], processed_len: u64) -> Result<Self>;
+
+    fn hash(data: &[u8]) -> Vec<u8> {
+        let mut hash = Self::default();
+        hash.update(data);
+        hash.finalize_to_vec()
+    }
+}
+
+impl Hash for Sha1 {
+    const BLOCK_SIZE: usize = Sha1::BLOCK_SIZE;
+    const OUTPUT_SIZE: usize = Sha1::OUTPUT_SIZE;
+
+    fn update(&mut self, data: &[u8]) {
+        Sha1::update(self, data)
+    }
+
+    fn finalize_to_vec(self) -> Vec<u8> {
+        self.finalize().to_vec()
+    }
+
+    fn padding(message_len: u64) -> Vec<u8> {
+        md_padding(message_len, ByteOrder::BigEndian)
+    }
+
+    fn resume(digest: &[u8], processed_len: u64) -> Result<Self> {
+        Sha1::from_digest(&output_array(digest)?, processed_len)
+    }
+}
+
+impl Hash for Md4 {
+    const BLOCK_SIZE: usize = Md4::BLOCK_SIZE;
+    const OUTPUT_SIZE: usize = Md4::OUTPUT_SIZE;
+
+    fn update(&mut self, data: &[u8]) {
+        Md4::update(self, data)
+    }
+
+    fn finalize_to_vec(self) -> Vec<u8> {
+        self.finalize().to_vec()
+    }
+
+    fn padding(message_len: u64) -> Vec<u8> {
+        md_padding(message_len, ByteOrder::LittleEndian)
+    }
+
+    fn resume(digest: &[u8], processed_len: u64) -> Result<Self> {
+        Md4::from_digest(&output_array(digest)?, processed_len)
+    }
+}
@@ -0,0 +1,162 @@
+use crate::hash::merkle_damgard;
+use crate::hash::merkle_damgard::md_padding;
+use crate::hash::merkle_damgard::BlockBuffer;
+use crate::hash::merkle_damgard::ByteOrder;
+use crate::Result;
+
+const ORDER: ByteOrder = ByteOrder::BigEndian;
+
+const INITIAL_STATE: [u32; 5] = [
+    0x6745_2301,
+    0xefcd_ab89,
+    0x98ba_dcfe,
+    0x1032_5476,
+    0xc3d2_e1f0,
+];
+
+#[derive(Clone)]
+pub struct Sha1 {
+    state: [u32; 5],
+    blocks: BlockBuffer,
+}
+
+impl Sha1 {
+    pub const BLOCK_SIZE: usize = merkle_damgard::BLOCK_SIZE;
+    pub const OUTPUT_SIZE: usize = 20;
+
+    pub fn new() -> Sha1 {
+        Sha1 {
+            state: INITIAL_STATE,
+            blocks: BlockBuffer::new(),
+        }
+    }
+
+    // Resumes hashing from an intermediate state reached after `processed_len` bytes.
+    pub fn from_state(state: [u32; 5], processed_len: u64) -> Result<Sha1> {
+        Ok(Sha1 {
+            state,
+            blocks: BlockBuffer::resume(processed_len)?,
+        })
+    }
+
+    // The digest is just the serialised final state, so it can be resumed too.
+    pub fn from_digest(digest: &[u8; 20], processed_len: u64) -> Result<Sha1> {
+        let mut state = [0u32; 5];
+        ORDER.read_words(digest, &mut state);
+        Sha1::from_state(state, processed_len)
+    }
+
+    pub fn state(&self) -> [u32; 5] {
+        self.state
+    }
+
+    pub fn update(&mut self, data: &[u8]) {
+        let state = &mut self.state;
+        self.blocks.update(data, |block| compress(state, block));
+    }
+
+    pub fn finalize(mut self) -> [u8; 20] {
+        let padding = md_padding(self.blocks.length(), ORDER);
+        self.update(&padding);
+
+        let mut digest = [0u8; 20];
+        ORDER.write_words(&self.state, &mut digest);
+        digest
+    }
+
+    pub fn digest(data: &[u8]) -> [u8; 20] {
+        let mut sha1 = Sha1::new();
+        sha1.update(data);
+        sha1.finalize()
+    }
+}
+
+impl Default for Sha1 {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn compress(state: &mut [u32; 5], block: &[u8]) {
+    let mut w = [0u32; 80];
+    ORDER.read_words(block, &mut w[..16]);
+    for i in 16..80 {
+        w[i] = (w[i - 3] ^ w[i - 8] ^ w[i - 14] ^ w[i - 16]).rotate_left(1);
+    }
+
+    let [mut a, mut b, mut c, mut d, mut e] = *state;
+
+    for (i, word) in w.iter().enumerate() {
+        let (f, k) = match i {
+            0..=19 => ((b & c) | (!b & d), 0x5a82_7999),
+            20..=39 => (b ^ c ^ d, 0x6ed9_eba1),
+            40..=59 => ((b & c) | (b & d) | (c & d), 0x8f1b_bcdc),
+            _ => (b ^ c ^ d, 0xca62_c1d6),
+        };
+
+        let temp = a
+            .rotate_left(5)
+            .wrapping_add(f)
+            .wrapping_add(e)
+            .wrapping_add(k)
+            .wrapping_add(*word);
+        e = d;
+        d = c;
+        c = b.rotate_left(30);
+        b = a;
+        a = temp;
+    }
+
+    for (word, value) in state.iter_mut().zip([a, b, c, d, e].iter()) {
+        *word = word.wrapping_add(*value);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Sha1;
+
+    // FIPS 180-2, Appendix A.
+    #[test]
+    fn nist_vectors() {
+        let vectors: [(&[u8], &str); 3] = [
+            (b"", "da39a3ee5e6b4b0d3255bfef95601890afd80709"),
+            (b"abc", "a9993e364706816aba3e25717850c26c9cd0d89d"),
+            (
+                b"abcdbcdecdefdefgefghfghighijhijkijkljklmklmnlmnomnopnopq",
+                "84983e441c3bd26ebaae4aa1f95129e5e54670f1",
+            ),
+        ];
+
+        for (message, digest) in vectors.iter() {
+            assert_eq!(hex::encode(Sha1::digest(message)), *digest);
+        }
+    }
+
+    #[test]
+    fn nist_million_a_streaming() {
+        let mut sha1 = Sha1::new();
+        for _ in 0..1000 {
+            sha1.update(&[b'a'; 1000]);
+        }
+
+        assert_eq!(
+            hex::encode(sha1.finalize()),
+            "34aa973cd4c4daa4f61eeb2bdbad27316534016f"
+        );
+    }
+
+    #[test]
+    fn resume_from_state() {
+        let message = [0x5au8; 200];
+
+        let mut prefix = Sha1::new();
+        prefix.update(&message[..128]);
+
+        let mut resumed = Sha1::from_state(prefix.state(), 128).unwrap();
+        resumed.update(&message[128..]);
+
+        assert_eq!(resumed.finalize(), Sha1::digest(&message));
+        assert!(Sha1::from_state(prefix.state(), 100).is_err());
+    }
+}
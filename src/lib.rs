@@ -1,3 +1,29 @@
+pub mod analysis;
+pub mod attacks;
+pub mod cipher;
+pub mod clock;
+pub mod dh;
+pub mod encoding;
+mod error;
+pub mod hash;
+pub mod mac;
+pub mod modes;
+pub mod network;
+pub mod padding;
+pub mod rng;
+pub mod xor;
+
+mod utils;
+
+pub use error::{Error, Result};
+
+#[cfg(test)]
+mod set_five;
+#[cfg(test)]
+mod set_four;
+#[cfg(test)]
 mod set_one;
+#[cfg(test)]
+mod set_three;
+#[cfg(test)]
 mod set_two;
-mod utils;
@@ -0,0 +1,75 @@
+use crate::hash::Hash;
+use crate::hash::Md4;
+use crate::hash::Sha1;
+
+// H(key || message): a secret-prefix MAC, and a poor one.
+pub fn keyed_mac<H: Hash>(key: &[u8], message: &[u8]) -> Vec<u8> {
+    let mut hash = H::default();
+    hash.update(key);
+    hash.update(message);
+    hash.finalize_to_vec()
+}
+
+pub fn verify_keyed_mac<H: Hash>(key: &[u8], message: &[u8], mac: &[u8]) -> bool {
+    keyed_mac::<H>(key, message) == mac
+}
+
+pub fn sha1_keyed_mac(key: &[u8], message: &[u8]) -> Vec<u8> {
+    keyed_mac::<Sha1>(key, message)
+}
+
+pub fn verify_sha1_keyed_mac(key: &[u8], message: &[u8], mac: &[u8]) -> bool {
+    verify_keyed_mac::<Sha1>(key, message, mac)
+}
+
+pub fn md4_keyed_mac(key: &[u8], message: &[u8]) -> Vec<u8> {
+    keyed_mac::<Md4>(key, message)
+}
+
+// RFC 2104: H((K ^ opad) || H((K ^ ipad) || message)), with over-long keys hashed first.
+pub fn hmac<H: Hash>(key: &[u8], message: &[u8]) -> Vec<u8> {
+    let mut block_key = if key.len() > H::BLOCK_SIZE {
+        H::hash(key)
+    } else {
+        key.to_vec()
+    };
+    block_key.resize(H::BLOCK_SIZE, 0);
+
+    let mut inner = H::default();
+    inner.update(&block_key.iter().map(|byte| byte ^ 0x36).collect::<Vec<_>>());
+    inner.update(message);
+
+    let mut outer = H::default();
+    outer.update(&block_key.iter().map(|byte| byte ^ 0x5c).collect::<Vec<_>>());
+    outer.update(&inner.finalize_to_vec());
+    outer.finalize_to_vec()
+}
+
+pub fn hmac_sha1(key: &[u8], message: &[u8]) -> Vec<u8> {
+    hmac::<Sha1>(key, message)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::hmac_sha1;
+
+    // RFC 2202, test cases 1, 2 and 6.
+    #[test]
+    fn hmac_sha1_vectors() {
+        assert_eq!(
+            hex::encode(hmac_sha1(&[0x0b; 20], b"Hi There")),
+            "b617318655057264e28bc0b6fb378c8ef146be00"
+        );
+        assert_eq!(
+            hex::encode(hmac_sha1(b"Jefe", b"what do ya want for nothing?")),
+            "effcdf6ae5eb2fa2d27416d5f184df9c259a7c79"
+        );
+        assert_eq!(
+            hex::encode(hmac_sha1(
+                &[0xaa; 80],
+                b"Test Using Larger Than Block-Size Key - Hash Key First"
+            )),
+            "aa4ae5e15272d00e95705637ce8a3b55ed402112"
+        );
+    }
+}
@@ -0,0 +1,69 @@
+use crate::cipher::BlockCipher;
+use crate::modes::check_block_aligned;
+use crate::modes::check_iv;
+use crate::modes::CipherMode;
+use crate::padding::pad_pkcs7;
+use crate::padding::unpad_pkcs7;
+use crate::xor::fixed_xor;
+use crate::Result;
+
+pub struct Cbc<C> {
+    cipher: C,
+    iv: Vec<u8>,
+}
+
+impl<C: BlockCipher> Cbc<C> {
+    pub fn new(cipher: C, iv: &[u8]) -> Result<Cbc<C>> {
+        check_iv(&cipher, iv)?;
+
+        Ok(Cbc {
+            cipher,
+            iv: iv.to_vec(),
+        })
+    }
+
+    pub fn cipher(&self) -> &C {
+        &self.cipher
+    }
+
+    pub fn iv(&self) -> &[u8] {
+        &self.iv
+    }
+}
+
+// A: Encrypted(block_0 ^ IV) + B: Encrypted(block_1 ^ A) + C: Encrypted(block_2 ^ B)
+// block_0: Decrypted(A) ^ IV + block_1: Decrypted(B) ^ A + block_2: Decrypted(C) ^ block_1
+impl<C: BlockCipher> CipherMode for Cbc<C> {
+    fn encrypt(&self, data: &[u8]) -> Result<Vec<u8>> {
+        let block_size = self.cipher.block_size();
+        let mut out = data.to_vec();
+        pad_pkcs7(&mut out, block_size as u8)?;
+
+        let mut previous_block = self.iv.clone();
+        for block in out.chunks_mut(block_size) {
+            fixed_xor(block, &previous_block)?;
+            self.cipher.encrypt_block(block)?;
+            previous_block = block.to_vec();
+        }
+
+        Ok(out)
+    }
+
+    fn decrypt(&self, data: &[u8]) -> Result<Vec<u8>> {
+        let block_size = self.cipher.block_size();
+        check_block_aligned(data, block_size)?;
+
+        let mut out = data.to_vec();
+        let mut previous_block = self.iv.clone();
+        for block in out.chunks_mut(block_size) {
+            let encrypted = block.to_vec();
+            self.cipher.decrypt_block(block)?;
+            fixed_xor(block, &previous_block)?;
+            previous_block = encrypted;
+        }
+
+        unpad_pkcs7(&mut out, block_size as u8)?;
+
+        Ok(out)
+    }
+}
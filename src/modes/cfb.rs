@@ -0,0 +1,60 @@
+use crate::cipher::BlockCipher;
+use crate::modes::check_iv;
+use crate::modes::CipherMode;
+use crate::xor::fixed_xor;
+use crate::Result;
+
+// Full-block CFB: the feedback segment is the same size as the cipher block, and a trailing
+// partial block only uses as much of the final keystream block as it needs.
+pub struct Cfb<C> {
+    cipher: C,
+    iv: Vec<u8>,
+}
+
+impl<C: BlockCipher> Cfb<C> {
+    pub fn new(cipher: C, iv: &[u8]) -> Result<Cfb<C>> {
+        check_iv(&cipher, iv)?;
+
+        Ok(Cfb {
+            cipher,
+            iv: iv.to_vec(),
+        })
+    }
+
+    pub fn cipher(&self) -> &C {
+        &self.cipher
+    }
+
+    pub fn iv(&self) -> &[u8] {
+        &self.iv
+    }
+}
+
+impl<C: BlockCipher> CipherMode for Cfb<C> {
+    fn encrypt(&self, data: &[u8]) -> Result<Vec<u8>> {
+        let mut out = data.to_vec();
+        let mut register = self.iv.clone();
+
+        for segment in out.chunks_mut(self.cipher.block_size()) {
+            self.cipher.encrypt_block(&mut register)?;
+            fixed_xor(segment, &register[..segment.len()])?;
+            register[..segment.len()].copy_from_slice(segment);
+        }
+
+        Ok(out)
+    }
+
+    fn decrypt(&self, data: &[u8]) -> Result<Vec<u8>> {
+        let mut out = data.to_vec();
+        let mut register = self.iv.clone();
+
+        for segment in out.chunks_mut(self.cipher.block_size()) {
+            let encrypted = segment.to_vec();
+            self.cipher.encrypt_block(&mut register)?;
+            fixed_xor(segment, &register[..segment.len()])?;
+            register[..segment.len()].copy_from_slice(&encrypted);
+        }
+
+        Ok(out)
+    }
+}
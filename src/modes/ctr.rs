@@ -0,0 +1,170 @@
+use crate::cipher::Aes;
+use crate::cipher::BlockCipher;
+use crate::modes::CipherMode;
+use crate::xor::fixed_xor;
+use crate::Error;
+use crate::Result;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CounterLayout {
+    // 64-bit little-endian nonce followed by a 64-bit little-endian block counter, as used
+    // throughout the cryptopals challenges.
+    LittleEndian64,
+    // 96-bit nonce followed by a 32-bit big-endian block counter, as used by GCM.
+    BigEndian96,
+}
+
+impl CounterLayout {
+    pub fn nonce_len(self) -> usize {
+        match self {
+            CounterLayout::LittleEndian64 => 8,
+            CounterLayout::BigEndian96 => 12,
+        }
+    }
+}
+
+pub struct Ctr<C> {
+    cipher: C,
+    nonce: Vec<u8>,
+    layout: CounterLayout,
+    initial_counter: u64,
+}
+
+impl<C: BlockCipher> Ctr<C> {
+    pub fn new(cipher: C, nonce: u64) -> Result<Ctr<C>> {
+        Self::with_layout(cipher, &nonce.to_le_bytes(), CounterLayout::LittleEndian64)
+    }
+
+    pub fn with_layout(cipher: C, nonce: &[u8], layout: CounterLayout) -> Result<Ctr<C>> {
+        if cipher.block_size() != 16 {
+            return Err(Error::InvalidBlockSize(cipher.block_size()));
+        }
+
+        if nonce.len() != layout.nonce_len() {
+            return Err(Error::InvalidIvSize(nonce.len()));
+        }
+
+        Ok(Ctr {
+            cipher,
+            nonce: nonce.to_vec(),
+            layout,
+            initial_counter: 0,
+        })
+    }
+
+    pub fn with_initial_counter(mut self, counter: u64) -> Result<Ctr<C>> {
+        if self.layout == CounterLayout::BigEndian96 && counter > u32::MAX as u64 {
+            return Err(Error::InvalidCounter(counter));
+        }
+
+        self.initial_counter = counter;
+        Ok(self)
+    }
+
+    pub fn cipher(&self) -> &C {
+        &self.cipher
+    }
+
+    pub fn nonce(&self) -> &[u8] {
+        &self.nonce
+    }
+
+    pub fn layout(&self) -> CounterLayout {
+        self.layout
+    }
+
+    fn keystream_block(&self, block_index: u64) -> Result<Vec<u8>> {
+        let counter = self.initial_counter.wrapping_add(block_index);
+
+        let mut block = self.nonce.clone();
+        match self.layout {
+            CounterLayout::LittleEndian64 => block.extend_from_slice(&counter.to_le_bytes()),
+            // Past 2^32 blocks the 32-bit counter wraps, as GCM's inc32 does.
+            CounterLayout::BigEndian96 => block.extend_from_slice(&(counter as u32).to_be_bytes()),
+        }
+        self.cipher.encrypt_block(&mut block)?;
+
+        Ok(block)
+    }
+
+    // XORs the keystream starting at byte `offset` into `data`, so any part of a message can be
+    // encrypted or decrypted without touching what comes before it. The whole range has to be
+    // addressable, so a span running past the last byte of a 64-bit offset is rejected.
+    pub fn apply_keystream(&self, offset: u64, data: &mut [u8]) -> Result<()> {
+        if offset.checked_add(data.len() as u64).is_none() {
+            return Err(Error::InvalidOffset(offset));
+        }
+
+        let block_size = self.cipher.block_size() as u64;
+        let mut position = offset;
+        let mut remaining = data;
+
+        while !remaining.is_empty() {
+            let skip = (position % block_size) as usize;
+            let keystream = self.keystream_block(position / block_size)?;
+            let take = remaining.len().min(keystream.len() - skip);
+
+            let (segment, rest) = remaining.split_at_mut(take);
+            fixed_xor(segment, &keystream[skip..skip + take])?;
+
+            position += take as u64;
+            remaining = rest;
+        }
+
+        Ok(())
+    }
+
+    // Re-encrypts `newtext` over the plaintext at `offset` without touching anything else,
+    // extending the ciphertext if the new text runs past its end.
+    pub fn edit(&self, ciphertext: &[u8], offset: usize, newtext: &[u8]) -> Result<Vec<u8>> {
+        if offset > ciphertext.len() {
+            return Err(Error::LengthMismatch {
+                expected: ciphertext.len(),
+                actual: offset,
+            });
+        }
+
+        let mut segment = newtext.to_vec();
+        self.apply_keystream(offset as u64, &mut segment)?;
+
+        let mut out = ciphertext.to_vec();
+        let end = offset + segment.len();
+        if end > out.len() {
+            out.resize(end, 0);
+        }
+        out[offset..end].copy_from_slice(&segment);
+
+        Ok(out)
+    }
+
+    pub fn keystream(&self, offset: u64, len: usize) -> Result<Vec<u8>> {
+        let mut keystream = vec![0u8; len];
+        self.apply_keystream(offset, &mut keystream)?;
+
+        Ok(keystream)
+    }
+}
+
+impl<C: BlockCipher> CipherMode for Ctr<C> {
+    fn encrypt(&self, data: &[u8]) -> Result<Vec<u8>> {
+        let mut out = data.to_vec();
+        self.apply_keystream(0, &mut out)?;
+
+        Ok(out)
+    }
+
+    fn decrypt(&self, data: &[u8]) -> Result<Vec<u8>> {
+        self.encrypt(data)
+    }
+}
+
+// Random-access edit of an AES-CTR ciphertext produced with the cryptopals layout.
+pub fn edit(
+    ciphertext: &[u8],
+    key: &[u8],
+    nonce: u64,
+    offset: usize,
+    newtext: &[u8],
+) -> Result<Vec<u8>> {
+    Ctr::new(Aes::new(key)?, nonce)?.edit(ciphertext, offset, newtext)
+}
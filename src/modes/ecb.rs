@@ -0,0 +1,48 @@
+use crate::cipher::BlockCipher;
+use crate::modes::check_block_aligned;
+use crate::modes::CipherMode;
+use crate::padding::pad_pkcs7;
+use crate::padding::unpad_pkcs7;
+use crate::Result;
+
+pub struct Ecb<C> {
+    cipher: C,
+}
+
+impl<C: BlockCipher> Ecb<C> {
+    pub fn new(cipher: C) -> Ecb<C> {
+        Ecb { cipher }
+    }
+
+    pub fn cipher(&self) -> &C {
+        &self.cipher
+    }
+}
+
+impl<C: BlockCipher> CipherMode for Ecb<C> {
+    fn encrypt(&self, data: &[u8]) -> Result<Vec<u8>> {
+        let block_size = self.cipher.block_size();
+        let mut out = data.to_vec();
+        pad_pkcs7(&mut out, block_size as u8)?;
+
+        for block in out.chunks_mut(block_size) {
+            self.cipher.encrypt_block(block)?;
+        }
+
+        Ok(out)
+    }
+
+    fn decrypt(&self, data: &[u8]) -> Result<Vec<u8>> {
+        let block_size = self.cipher.block_size();
+        check_block_aligned(data, block_size)?;
+
+        let mut out = data.to_vec();
+        for block in out.chunks_mut(block_size) {
+            self.cipher.decrypt_block(block)?;
+        }
+
+        unpad_pkcs7(&mut out, block_size as u8)?;
+
+        Ok(out)
+    }
+}
@@ -0,0 +1,226 @@
+pub mod cbc;
+pub mod cfb;
+pub mod ctr;
+pub mod ecb;
+pub mod ofb;
+
+pub use cbc::Cbc;
+pub use cfb::Cfb;
+pub use ctr::CounterLayout;
+pub use ctr::Ctr;
+pub use ecb::Ecb;
+pub use ofb::Ofb;
+
+use crate::cipher::Aes;
+use crate::cipher::BlockCipher;
+use crate::Error;
+use crate::Result;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Mode {
+    Encrypt,
+    Decrypt,
+}
+
+pub trait CipherMode {
+    fn encrypt(&self, data: &[u8]) -> Result<Vec<u8>>;
+
+    fn decrypt(&self, data: &[u8]) -> Result<Vec<u8>>;
+
+    fn crypt(&self, data: &[u8], mode: Mode) -> Result<Vec<u8>> {
+        match mode {
+            Mode::Encrypt => self.encrypt(data),
+            Mode::Decrypt => self.decrypt(data),
+        }
+    }
+}
+
+pub(crate) fn check_block_aligned(data: &[u8], block_size: usize) -> Result<()> {
+    if !data.len().is_multiple_of(block_size) {
+        return Err(Error::NotBlockAligned {
+            len: data.len(),
+            block_size,
+        });
+    }
+
+    Ok(())
+}
+
+pub(crate) fn check_iv<C: BlockCipher>(cipher: &C, iv: &[u8]) -> Result<()> {
+    if iv.len() != cipher.block_size() {
+        return Err(Error::InvalidIvSize(iv.len()));
+    }
+
+    Ok(())
+}
+
+fn aes_128(key: &[u8]) -> Result<Aes> {
+    if key.len() != 16 {
+        return Err(Error::InvalidKeySize(key.len()));
+    }
+
+    Aes::new(key)
+}
+
+pub fn decrypt_aes_ecb_128(key: &[u8], data: &[u8]) -> Result<Vec<u8>> {
+    Ecb::new(aes_128(key)?).decrypt(data)
+}
+
+pub fn aes_128_cbc_crypter(key: &[u8], iv: &[u8], data: &[u8], mode: Mode) -> Result<Vec<u8>> {
+    Cbc::new(aes_128(key)?, iv)?.crypt(data, mode)
+}
+
+// Reusing the key as the IV, as some protocols do to avoid sending an IV. Don't.
+pub fn aes_128_cbc_key_as_iv_crypter(key: &[u8], data: &[u8], mode: Mode) -> Result<Vec<u8>> {
+    aes_128_cbc_crypter(key, key, data, mode)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Cbc, Cfb, CipherMode, CounterLayout, Ctr, Ecb, Ofb};
+    use crate::cipher::{Aes, BlockCipher};
+
+    // NIST SP 800-38A, Appendix F (AES-128).
+    const KEY: &str = "2b7e151628aed2a6abf7158809cf4f3c";
+    const IV: &str = "000102030405060708090a0b0c0d0e0f";
+    const PLAINTEXT: &str = "6bc1bee22e409f96e93d7e117393172aae2d8a571e03ac9c9eb76fac45af8e5130c81c46a35ce411e5fbc1191a0a52eff69f2445df4f9b17ad2b417be66c3710";
+
+    fn aes() -> Aes {
+        Aes::new(&hex::decode(KEY).unwrap()).unwrap()
+    }
+
+    fn check_vector<M: CipherMode>(mode: M, ciphertext: &str) {
+        let plaintext = hex::decode(PLAINTEXT).unwrap();
+        let encrypted = mode.encrypt(&plaintext).unwrap();
+
+        assert_eq!(hex::encode(&encrypted[..plaintext.len()]), ciphertext);
+        assert_eq!(mode.decrypt(&encrypted).unwrap(), plaintext);
+    }
+
+    #[test]
+    fn sp_800_38a_ecb() {
+        check_vector(Ecb::new(aes()), "3ad77bb40d7a3660a89ecaf32466ef97f5d3d58503b9699de785895a96fdbaaf43b1cd7f598ece23881b00e3ed0306887b0c785e27e8ad3f8223207104725dd4");
+    }
+
+    #[test]
+    fn sp_800_38a_cbc() {
+        let iv = hex::decode(IV).unwrap();
+        check_vector(Cbc::new(aes(), &iv).unwrap(), "7649abac8119b246cee98e9b12e9197d5086cb9b507219ee95db113a917678b273bed6b8e3c1743b7116e69e222295163ff1caa1681fac09120eca307586e1a7");
+    }
+
+    #[test]
+    fn sp_800_38a_cfb128() {
+        let iv = hex::decode(IV).unwrap();
+        check_vector(Cfb::new(aes(), &iv).unwrap(), "3b3fd92eb72dad20333449f8e83cfb4ac8a64537a0b3a93fcde3cdad9f1ce58b26751f67a3cbb140b1808cf187a4f4dfc04b05357c5d1c0eeac4c66f9ff7f2e6");
+    }
+
+    #[test]
+    fn sp_800_38a_ofb() {
+        let iv = hex::decode(IV).unwrap();
+        check_vector(Ofb::new(aes(), &iv).unwrap(), "3b3fd92eb72dad20333449f8e83cfb4a7789508d16918f03f53c52dac54ed8259740051e9c5fecf64344f7a82260edcc304c6528f659c77866a510d9c1d6ae5e");
+    }
+
+    #[test]
+    fn sp_800_38a_ctr() {
+        let counter_block = hex::decode("f0f1f2f3f4f5f6f7f8f9fafbfcfdfeff").unwrap();
+        let ctr = Ctr::with_layout(aes(), &counter_block[..12], CounterLayout::BigEndian96)
+            .unwrap()
+            .with_initial_counter(0xfcfdfeff)
+            .unwrap();
+
+        check_vector(ctr, "874d6191b620e3261bef6864990db6ce9806f66b7970fdff8617187bb9fffdff5ae4df3edbd5d35e5b4f09020db03eab1e031dda2fbe03d1792170a0f3009cee");
+    }
+
+    #[test]
+    fn ctr_counter_range() {
+        let big_endian = || Ctr::with_layout(aes(), &[0; 12], CounterLayout::BigEndian96).unwrap();
+        assert!(big_endian().with_initial_counter(u32::MAX as u64).is_ok());
+        assert!(big_endian().with_initial_counter(1 << 32).is_err());
+
+        let little_endian = Ctr::new(aes(), 0).unwrap();
+        assert!(little_endian.with_initial_counter(1 << 32).is_ok());
+    }
+
+    #[test]
+    fn ctr_seekable_keystream() {
+        let ctr = Ctr::new(aes(), 42).unwrap();
+        let keystream = ctr.keystream(0, 100).unwrap();
+
+        for offset in [0usize, 1, 15, 16, 17, 47, 99].iter() {
+            let len = 100 - offset;
+            assert_eq!(
+                ctr.keystream(*offset as u64, len).unwrap(),
+                &keystream[*offset..]
+            );
+        }
+
+        assert!(ctr.keystream(u64::MAX - 40, 40).is_ok());
+        assert!(ctr.keystream(u64::MAX - 3, 40).is_err());
+    }
+
+    #[test]
+    fn ctr_edit() {
+        let key = hex::decode(KEY).unwrap();
+        let ctr = Ctr::new(aes(), 7).unwrap();
+        let ciphertext = ctr.encrypt(b"attack at dawn").unwrap();
+
+        let edited = super::ctr::edit(&ciphertext, &key, 7, 10, b"dusk!").unwrap();
+        assert_eq!(ctr.decrypt(&edited).unwrap(), b"attack at dusk!");
+        assert!(super::ctr::edit(&ciphertext, &key, 7, 15, b"x").is_err());
+    }
+
+    #[test]
+    fn ctr_keystream_layout() {
+        let ctr = Ctr::new(aes(), 0x0102030405060708).unwrap();
+        let keystream = ctr.encrypt(&[0u8; 40]).unwrap();
+
+        for (counter, block) in keystream.chunks(16).enumerate() {
+            let mut expected = [0u8; 16];
+            expected[..8].copy_from_slice(&0x0102030405060708u64.to_le_bytes());
+            expected[8..].copy_from_slice(&(counter as u64).to_le_bytes());
+            aes().encrypt_block(&mut expected).unwrap();
+
+            assert_eq!(block, &expected[..block.len()]);
+        }
+    }
+
+    #[test]
+    fn stream_modes_handle_partial_blocks() {
+        let iv = hex::decode(IV).unwrap();
+        let plaintext = &hex::decode(PLAINTEXT).unwrap()[..37];
+
+        let cfb = Cfb::new(aes(), &iv).unwrap();
+        let ofb = Ofb::new(aes(), &iv).unwrap();
+        let ctr = Ctr::new(aes(), 0).unwrap();
+
+        assert_eq!(
+            cfb.decrypt(&cfb.encrypt(plaintext).unwrap()).unwrap(),
+            plaintext
+        );
+        assert_eq!(
+            ofb.decrypt(&ofb.encrypt(plaintext).unwrap()).unwrap(),
+            plaintext
+        );
+        assert_eq!(
+            ctr.decrypt(&ctr.encrypt(plaintext).unwrap()).unwrap(),
+            plaintext
+        );
+    }
+
+    #[test]
+    fn test_encrypt_decrypt() {
+        let key: [u8; 16] = [
+            13, 37, 13, 37, 13, 37, 13, 37, 13, 37, 13, 37, 13, 37, 13, 37,
+        ];
+        let iv: [u8; 16] = [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15];
+
+        let data = "Lorem ipsum dolor sit amet, consectetur adipiscing elit. Donec scelerisque posuere nisl, in varius justo porttitor vel. Duis aliquet, est at fringilla laoreet, enim nibh facilisis lacus, quis aliquam magna mauris quis erat. Donec molestie dui et scelerisque vulputate. Cras molestie fermentum lectus, non suscipit mi. Mauris auctor scelerisque nunc eu rhoncus. Ut gravida, erat et suscipit accumsan, lectus purus sodales lorem, vel tincidunt mi metus in leo. Praesent luctus nulla quis est placerat, vitae varius purus sodales. Fusce scelerisque arcu velit, sit amet ullamcorper lectus consequat at. Donec dolor purus, semper a urna imperdiet, pharetra molestie elit. Pellentesque habitant morbi tristique senectus et netus et malesuada fames ac turpis egestas. Nam vel pretium sapien. Suspendisse imperdiet tempor purus. Integer a molestie ligula.".as_bytes();
+
+        let encrypted = super::aes_128_cbc_crypter(&key, &iv, data, super::Mode::Encrypt).unwrap();
+
+        assert_eq!(
+            data,
+            super::aes_128_cbc_crypter(&key, &iv, &encrypted, super::Mode::Decrypt).unwrap()
+        );
+    }
+}
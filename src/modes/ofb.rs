@@ -0,0 +1,47 @@
+use crate::cipher::BlockCipher;
+use crate::modes::check_iv;
+use crate::modes::CipherMode;
+use crate::xor::fixed_xor;
+use crate::Result;
+
+pub struct Ofb<C> {
+    cipher: C,
+    iv: Vec<u8>,
+}
+
+impl<C: BlockCipher> Ofb<C> {
+    pub fn new(cipher: C, iv: &[u8]) -> Result<Ofb<C>> {
+        check_iv(&cipher, iv)?;
+
+        Ok(Ofb {
+            cipher,
+            iv: iv.to_vec(),
+        })
+    }
+
+    pub fn cipher(&self) -> &C {
+        &self.cipher
+    }
+
+    pub fn iv(&self) -> &[u8] {
+        &self.iv
+    }
+}
+
+impl<C: BlockCipher> CipherMode for Ofb<C> {
+    fn encrypt(&self, data: &[u8]) -> Result<Vec<u8>> {
+        let mut out = data.to_vec();
+        let mut keystream = self.iv.clone();
+
+        for segment in out.chunks_mut(self.cipher.block_size()) {
+            self.cipher.encrypt_block(&mut keystream)?;
+            fixed_xor(segment, &keystream[..segment.len()])?;
+        }
+
+        Ok(out)
+    }
+
+    fn decrypt(&self, data: &[u8]) -> Result<Vec<u8>> {
+        self.encrypt(data)
+    }
+}
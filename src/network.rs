@@ -0,0 +1,70 @@
+use crate::Result;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Direction {
+    ToResponder,
+    ToInitiator,
+}
+
+// One party to a two-party protocol. Returning `None` ends the conversation.
+pub trait Actor<M> {
+    fn receive(&mut self, message: M) -> Result<Option<M>>;
+}
+
+pub trait Initiator<M>: Actor<M> {
+    fn start(&mut self) -> Result<M>;
+}
+
+// Sits on the wire and sees every message before it is delivered, free to rewrite it.
+pub trait Relay<M> {
+    fn relay(&mut self, message: M, direction: Direction) -> Result<M>;
+}
+
+pub struct PassThrough;
+
+impl<M> Relay<M> for PassThrough {
+    fn relay(&mut self, message: M, _: Direction) -> Result<M> {
+        Ok(message)
+    }
+}
+
+// Shuttles messages back and forth through `relay` until either side stops replying, returning
+// how many messages were delivered.
+pub fn run<M, I, R, A>(initiator: &mut I, relay: &mut R, responder: &mut A) -> Result<usize>
+where
+    I: Initiator<M>,
+    R: Relay<M>,
+    A: Actor<M>,
+{
+    let mut message = initiator.start()?;
+    let mut direction = Direction::ToResponder;
+    let mut delivered = 0;
+
+    loop {
+        let delivery = relay.relay(message, direction)?;
+        delivered += 1;
+
+        let reply = match direction {
+            Direction::ToResponder => responder.receive(delivery)?,
+            Direction::ToInitiator => initiator.receive(delivery)?,
+        };
+
+        match reply {
+            Some(reply) => message = reply,
+            None => return Ok(delivered),
+        }
+
+        direction = match direction {
+            Direction::ToResponder => Direction::ToInitiator,
+            Direction::ToInitiator => Direction::ToResponder,
+        };
+    }
+}
+
+pub fn run_direct<M, I, A>(initiator: &mut I, responder: &mut A) -> Result<usize>
+where
+    I: Initiator<M>,
+    A: Actor<M>,
+{
+    run(initiator, &mut PassThrough, responder)
+}
@@ -0,0 +1,98 @@
+use crate::Error;
+use crate::Result;
+
+pub fn pad_pkcs7(data: &mut Vec<u8>, block_size: u8) -> Result<()> {
+    if block_size == 0 {
+        return Err(Error::InvalidBlockSize(block_size as usize));
+    }
+
+    // Aligned input still gets a full block of padding, so unpadding is never ambiguous.
+    let padding_length = block_size - (data.len() % block_size as usize) as u8;
+    data.extend((0..padding_length).map(|_| padding_length));
+
+    Ok(())
+}
+
+pub fn unpad_pkcs7(data: &mut Vec<u8>, block_size: u8) -> Result<()> {
+    if block_size == 0 {
+        return Err(Error::InvalidBlockSize(block_size as usize));
+    }
+
+    let len = data.len();
+    if len == 0 || !len.is_multiple_of(block_size as usize) {
+        return Err(Error::BadPadding);
+    }
+
+    let padding_length = data[len - 1];
+    if padding_length == 0 || padding_length > block_size {
+        return Err(Error::BadPadding);
+    }
+
+    let start = len - padding_length as usize;
+    if !data[start..].iter().all(|x| *x == padding_length) {
+        return Err(Error::BadPadding);
+    }
+
+    data.truncate(start);
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Error;
+    use proptest::prelude::*;
+
+    #[test]
+    fn pad_aligned_input_adds_full_block() {
+        let mut data = vec![0u8; 16];
+        super::pad_pkcs7(&mut data, 16).unwrap();
+
+        assert_eq!(&data[16..], &[16u8; 16][..]);
+    }
+
+    #[test]
+    fn unpad_full_block() {
+        let mut data = b"YELLOW SUBMARINE".to_vec();
+        data.extend(&[16u8; 16]);
+        super::unpad_pkcs7(&mut data, 16).unwrap();
+
+        assert_eq!(data, b"YELLOW SUBMARINE");
+    }
+
+    #[test]
+    fn unpad_rejects_malformed_padding() {
+        let cases: [&[u8]; 5] = [
+            b"",
+            b"YELLOW SUBMARINE",
+            b"YELLOW SUBMARIN\x00",
+            b"YELLOW SUBMARIN\x11",
+            b"YELLOW SUBMA\x04\x04\x04",
+        ];
+
+        for case in cases.iter() {
+            let mut data = case.to_vec();
+            match super::unpad_pkcs7(&mut data, 16) {
+                Err(Error::BadPadding) => assert_eq!(&data[..], *case),
+                other => panic!("unexpected result for {:?}: {:?}", case, other),
+            }
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn pad_unpad_round_trip(
+            data in proptest::collection::vec(any::<u8>(), 0..256),
+            block_size in 1u8..=255,
+        ) {
+            let mut padded = data.clone();
+            super::pad_pkcs7(&mut padded, block_size).unwrap();
+
+            prop_assert!(padded.len() > data.len());
+            prop_assert_eq!(padded.len() % block_size as usize, 0);
+
+            super::unpad_pkcs7(&mut padded, block_size).unwrap();
+            prop_assert_eq!(padded, data);
+        }
+    }
+}
@@ -0,0 +1,3 @@
+pub mod mt19937;
+
+pub use mt19937::{Mt19937, Mt19937_64};
@@ -0,0 +1,279 @@
+pub const N: usize = 624;
+pub(crate) const M: usize = 397;
+pub(crate) const MATRIX_A: u32 = 0x9908_b0df;
+const UPPER_MASK: u32 = 0x8000_0000;
+const LOWER_MASK: u32 = 0x7fff_ffff;
+
+const N_64: usize = 312;
+const M_64: usize = 156;
+const MATRIX_A_64: u64 = 0xb502_6f5a_a966_19e9;
+const UPPER_MASK_64: u64 = 0xffff_ffff_8000_0000;
+const LOWER_MASK_64: u64 = 0x7fff_ffff;
+
+pub const DEFAULT_SEED: u32 = 5489;
+
+// 32-bit Mersenne Twister, following the reference mt19937ar.c.
+#[derive(Clone)]
+pub struct Mt19937 {
+    state: [u32; N],
+    index: usize,
+}
+
+impl Mt19937 {
+    pub fn new(seed: u32) -> Mt19937 {
+        let mut state = [0u32; N];
+        state[0] = seed;
+
+        for i in 1..N {
+            state[i] = 1_812_433_253u32
+                .wrapping_mul(state[i - 1] ^ (state[i - 1] >> 30))
+                .wrapping_add(i as u32);
+        }
+
+        Mt19937 { state, index: N }
+    }
+
+    // Resumes a generator from a raw (untempered) state; `index` is the position of the next
+    // word to be tempered, so N means the state is twisted before the next output.
+    pub fn from_state(state: [u32; N], index: usize) -> Mt19937 {
+        Mt19937 {
+            state,
+            index: index.min(N),
+        }
+    }
+
+    pub fn init_by_array(key: &[u32]) -> Mt19937 {
+        let mut mt = Mt19937::new(19_650_218);
+        let state = &mut mt.state;
+        let (mut i, mut j) = (1, 0);
+
+        for _ in 0..N.max(key.len()) {
+            let previous = state[i - 1] ^ (state[i - 1] >> 30);
+            state[i] = (state[i] ^ previous.wrapping_mul(1_664_525))
+                .wrapping_add(key.get(j).copied().unwrap_or(0))
+                .wrapping_add(j as u32);
+
+            i += 1;
+            j += 1;
+            if i >= N {
+                state[0] = state[N - 1];
+                i = 1;
+            }
+            if j >= key.len() {
+                j = 0;
+            }
+        }
+
+        for _ in 0..N - 1 {
+            let previous = state[i - 1] ^ (state[i - 1] >> 30);
+            state[i] = (state[i] ^ previous.wrapping_mul(1_566_083_941)).wrapping_sub(i as u32);
+
+            i += 1;
+            if i >= N {
+                state[0] = state[N - 1];
+                i = 1;
+            }
+        }
+
+        state[0] = 0x8000_0000;
+        mt
+    }
+
+    fn twist(&mut self) {
+        for i in 0..N {
+            let y = (self.state[i] & UPPER_MASK) | (self.state[(i + 1) % N] & LOWER_MASK);
+            let mag = if y & 1 == 0 { 0 } else { MATRIX_A };
+            self.state[i] = self.state[(i + M) % N] ^ (y >> 1) ^ mag;
+        }
+
+        self.index = 0;
+    }
+
+    pub fn next_u32(&mut self) -> u32 {
+        if self.index >= N {
+            self.twist();
+        }
+
+        let y = self.state[self.index];
+        self.index += 1;
+
+        temper(y)
+    }
+
+    // Two consecutive outputs, low word first.
+    pub fn next_u64(&mut self) -> u64 {
+        let low = self.next_u32() as u64;
+        let high = self.next_u32() as u64;
+        (high << 32) | low
+    }
+}
+
+impl Default for Mt19937 {
+    fn default() -> Self {
+        Self::new(DEFAULT_SEED)
+    }
+}
+
+pub fn temper(mut y: u32) -> u32 {
+    y ^= y >> 11;
+    y ^= (y << 7) & 0x9d2c_5680;
+    y ^= (y << 15) & 0xefc6_0000;
+    y ^= y >> 18;
+    y
+}
+
+// 64-bit Mersenne Twister, following the reference mt19937-64.c.
+#[derive(Clone)]
+pub struct Mt19937_64 {
+    state: [u64; N_64],
+    index: usize,
+}
+
+impl Mt19937_64 {
+    pub fn new(seed: u64) -> Mt19937_64 {
+        let mut state = [0u64; N_64];
+        state[0] = seed;
+
+        for i in 1..N_64 {
+            state[i] = 6_364_136_223_846_793_005u64
+                .wrapping_mul(state[i - 1] ^ (state[i - 1] >> 62))
+                .wrapping_add(i as u64);
+        }
+
+        Mt19937_64 { state, index: N_64 }
+    }
+
+    pub fn init_by_array(key: &[u64]) -> Mt19937_64 {
+        let mut mt = Mt19937_64::new(19_650_218);
+        let state = &mut mt.state;
+        let (mut i, mut j) = (1, 0);
+
+        for _ in 0..N_64.max(key.len()) {
+            let previous = state[i - 1] ^ (state[i - 1] >> 62);
+            state[i] = (state[i] ^ previous.wrapping_mul(3_935_559_000_370_003_845))
+                .wrapping_add(key.get(j).copied().unwrap_or(0))
+                .wrapping_add(j as u64);
+
+            i += 1;
+            j += 1;
+            if i >= N_64 {
+                state[0] = state[N_64 - 1];
+                i = 1;
+            }
+            if j >= key.len() {
+                j = 0;
+            }
+        }
+
+        for _ in 0..N_64 - 1 {
+            let previous = state[i - 1] ^ (state[i - 1] >> 62);
+            state[i] = (state[i] ^ previous.wrapping_mul(2_862_933_555_777_941_757))
+                .wrapping_sub(i as u64);
+
+            i += 1;
+            if i >= N_64 {
+                state[0] = state[N_64 - 1];
+                i = 1;
+            }
+        }
+
+        state[0] = 1 << 63;
+        mt
+    }
+
+    fn twist(&mut self) {
+        for i in 0..N_64 {
+            let x = (self.state[i] & UPPER_MASK_64) | (self.state[(i + 1) % N_64] & LOWER_MASK_64);
+            let mag = if x & 1 == 0 { 0 } else { MATRIX_A_64 };
+            self.state[i] = self.state[(i + M_64) % N_64] ^ (x >> 1) ^ mag;
+        }
+
+        self.index = 0;
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        if self.index >= N_64 {
+            self.twist();
+        }
+
+        let mut x = self.state[self.index];
+        self.index += 1;
+
+        x ^= (x >> 29) & 0x5555_5555_5555_5555;
+        x ^= (x << 17) & 0x71d6_7fff_eda6_0000;
+        x ^= (x << 37) & 0xfff7_eee0_0000_0000;
+        x ^= x >> 43;
+        x
+    }
+
+    // The high half of the next 64-bit output.
+    pub fn next_u32(&mut self) -> u32 {
+        (self.next_u64() >> 32) as u32
+    }
+}
+
+impl Default for Mt19937_64 {
+    fn default() -> Self {
+        Self::new(DEFAULT_SEED as u64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Mt19937, Mt19937_64};
+
+    #[test]
+    fn default_seed() {
+        let mut mt = Mt19937::default();
+        assert_eq!(mt.next_u32(), 3_499_211_612);
+
+        // The 10000th output, as required of std::mt19937 by the C++ standard.
+        let mut mt = Mt19937::default();
+        let output = (0..10_000).map(|_| mt.next_u32()).last().unwrap();
+        assert_eq!(output, 4_123_659_995);
+    }
+
+    #[test]
+    fn default_seed_64() {
+        // The 10000th output, as required of std::mt19937_64 by the C++ standard.
+        let mut mt = Mt19937_64::default();
+        let output = (0..10_000).map(|_| mt.next_u64()).last().unwrap();
+        assert_eq!(output, 9_981_545_732_273_789_042);
+    }
+
+    #[test]
+    fn init_by_array() {
+        // First outputs of mt19937ar.out.
+        let mut mt = Mt19937::init_by_array(&[0x123, 0x234, 0x345, 0x456]);
+        let outputs = (0..5).map(|_| mt.next_u32()).collect::<Vec<_>>();
+
+        assert_eq!(
+            outputs,
+            [
+                1_067_595_299,
+                955_945_823,
+                477_289_528,
+                4_107_218_783,
+                4_228_976_476
+            ]
+        );
+    }
+
+    #[test]
+    fn init_by_array_64() {
+        // First outputs of mt19937-64.out.
+        let mut mt = Mt19937_64::init_by_array(&[0x12345, 0x23456, 0x34567, 0x45678]);
+        let outputs = (0..5).map(|_| mt.next_u64()).collect::<Vec<_>>();
+
+        assert_eq!(
+            outputs,
+            [
+                7_266_447_313_870_364_031,
+                4_946_485_549_665_804_864,
+                16_945_909_448_695_747_420,
+                16_394_063_075_524_226_720,
+                4_873_882_236_456_199_058
+            ]
+        );
+    }
+}
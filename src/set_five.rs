@@ -0,0 +1,68 @@
+use crate::attacks::dh_mitm::EchoClient;
+use crate::attacks::dh_mitm::EchoServer;
+use crate::attacks::dh_mitm::KeyFixingAttacker;
+use crate::dh::modexp;
+use crate::dh::DiffieHellman;
+use crate::dh::Group;
+use crate::modes::aes_128_cbc_crypter;
+use crate::modes::Mode;
+use crate::network::run;
+use crate::network::run_direct;
+use crate::utils::random_bytes;
+use num_bigint::BigUint;
+
+#[test]
+fn diffie_hellman_33() {
+    let group = Group::cryptopals_small();
+    let a = rand::random::<u32>() % 37;
+    let b = rand::random::<u32>() % 37;
+    let big_a = modexp(&group.g, &BigUint::from(a), &group.p);
+    let big_b = modexp(&group.g, &BigUint::from(b), &group.p);
+    assert_eq!(
+        modexp(&big_b, &BigUint::from(a), &group.p),
+        modexp(&big_a, &BigUint::from(b), &group.p)
+    );
+
+    let alice = DiffieHellman::new(Group::cryptopals_nist());
+    let bob = DiffieHellman::new(Group::cryptopals_nist());
+    assert_eq!(
+        alice.shared_secret(bob.public_key()),
+        bob.shared_secret(alice.public_key())
+    );
+
+    let alice_key = alice.session_key(bob.public_key());
+    let bob_key = bob.session_key(alice.public_key());
+    let iv = random_bytes(16);
+    let ciphertext = aes_128_cbc_crypter(&alice_key, &iv, b"Hello, Bob", Mode::Encrypt).unwrap();
+
+    assert_eq!(
+        aes_128_cbc_crypter(&bob_key, &iv, &ciphertext, Mode::Decrypt).unwrap(),
+        b"Hello, Bob"
+    );
+}
+
+#[test]
+fn dh_key_fixing_mitm_34() {
+    let messages = vec![
+        b"Hello, Bob".to_vec(),
+        b"Did you get the key?".to_vec(),
+        b"Nobody else can read this".to_vec(),
+    ];
+
+    let mut alice = EchoClient::new(Group::cryptopals_nist(), messages.clone());
+    let mut bob = EchoServer::new();
+    assert_eq!(run_direct(&mut alice, &mut bob).unwrap(), 8);
+    assert_eq!(alice.echoed(), &messages[..]);
+
+    let mut alice = EchoClient::new(Group::cryptopals_nist(), messages.clone());
+    let mut bob = EchoServer::new();
+    let mut mallory = KeyFixingAttacker::new();
+    run(&mut alice, &mut mallory, &mut bob).unwrap();
+
+    assert_eq!(alice.echoed(), &messages[..]);
+    let echoed_twice = messages
+        .iter()
+        .flat_map(|message| vec![message.clone(), message.clone()])
+        .collect::<Vec<_>>();
+    assert_eq!(mallory.intercepted(), &echoed_twice[..]);
+}
@@ -0,0 +1,137 @@
+use crate::attacks::bitflip::forge_ctr_admin;
+use crate::attacks::bitflip::CtrUserDataService;
+use crate::attacks::cbc_key_as_iv::recover_key_as_iv;
+use crate::attacks::cbc_key_as_iv::CbcReceiver;
+use crate::attacks::cbc_key_as_iv::KeyAsIvService;
+use crate::attacks::ctr_edit::recover_plaintext_via_edit;
+use crate::attacks::ctr_edit::CtrEditService;
+use crate::attacks::length_extension::forge_extension;
+use crate::attacks::length_extension::KeyedMacService;
+use crate::attacks::length_extension::MacOracle;
+use crate::attacks::timing_leak::recover_signature;
+use crate::attacks::timing_leak::HmacClient;
+use crate::attacks::timing_leak::HmacServer;
+use crate::attacks::timing_leak::SignatureOracle;
+use crate::hash::Hash;
+use crate::hash::Md4;
+use crate::hash::Sha1;
+use crate::mac::sha1_keyed_mac;
+use crate::mac::verify_sha1_keyed_mac;
+use crate::modes::decrypt_aes_ecb_128;
+use std::fs;
+use std::time::Duration;
+
+#[test]
+fn break_random_access_ctr_25() {
+    let mut encrypted = vec![];
+
+    for line in fs::read_to_string("challenge_data/set_one/7.txt")
+        .unwrap()
+        .lines()
+    {
+        encrypted.append(&mut base64::decode(line).unwrap())
+    }
+
+    let plaintext = decrypt_aes_ecb_128(b"YELLOW SUBMARINE", &encrypted).unwrap();
+    let service = CtrEditService::new().unwrap();
+    let ciphertext = service.encrypt(&plaintext).unwrap();
+
+    assert_eq!(
+        recover_plaintext_via_edit(&service, &ciphertext).unwrap(),
+        plaintext
+    );
+}
+
+#[test]
+fn ctr_bitflipping_26() {
+    let service = CtrUserDataService::new().unwrap();
+
+    let quoted = service.encrypt(b";admin=true;").unwrap();
+    assert!(!service.is_admin(&quoted).unwrap());
+
+    let forged = forge_ctr_admin(&service).unwrap();
+    assert!(service.is_admin(&forged).unwrap());
+}
+
+#[test]
+fn cbc_key_as_iv_27() {
+    let service = KeyAsIvService::new();
+    let ciphertext = service
+        .encrypt(b"comment1=cooking%20MCs;userdata=hello;comment2=%20like%20a%20pound%20of%20bacon")
+        .unwrap();
+    assert!(service.receive(&ciphertext).is_ok());
+
+    let key = recover_key_as_iv(&service, &ciphertext).unwrap();
+    assert!(service.is_key(&key));
+}
+
+#[test]
+fn sha1_keyed_mac_28() {
+    let key = b"YELLOW SUBMARINE";
+    let message = b"comment1=cooking%20MCs;userdata=foo;comment2=%20like%20a%20pound%20of%20bacon";
+    let mac = sha1_keyed_mac(key, message);
+
+    assert!(verify_sha1_keyed_mac(key, message, &mac));
+    assert!(!verify_sha1_keyed_mac(
+        key,
+        b"comment1=cooking%20MCs;userdata=bar",
+        &mac
+    ));
+    assert!(!verify_sha1_keyed_mac(b"YELLOW SUBMARINF", message, &mac));
+}
+
+fn check_length_extension<H: Hash>() {
+    let service = KeyedMacService::<H>::new();
+    let message = b"comment1=cooking%20MCs;userdata=foo;comment2=%20like%20a%20pound%20of%20bacon";
+    let mac = service.mac(message);
+
+    let (forged, forged_mac, _) =
+        forge_extension::<H, _>(&service, &mac, message, b";admin=true", 128).unwrap();
+
+    assert!(forged.starts_with(message));
+    assert!(forged.ends_with(b";admin=true"));
+    assert!(service.verify(&forged, &forged_mac));
+}
+
+#[test]
+fn sha1_length_extension_29() {
+    check_length_extension::<Sha1>();
+}
+
+#[test]
+fn md4_length_extension_30() {
+    check_length_extension::<Md4>();
+}
+
+// Both timing tests attack a truncated HMAC so they finish in seconds; the cost per byte grows
+// with its position, not with the signature length.
+#[test]
+fn hmac_timing_leak_31() {
+    let server = HmacServer::start_truncated(Duration::from_millis(20), 2).unwrap();
+    let client = HmacClient::new(server.addr(), "foo");
+
+    assert!(!client.check(&[0, 0]).unwrap());
+    let signature = recover_signature(&client, 2, 1).unwrap();
+    assert!(client.check(&signature).unwrap());
+}
+
+#[test]
+fn hmac_subtle_timing_leak_32() {
+    let server = HmacServer::start_truncated(Duration::from_millis(3), 3).unwrap();
+    let client = HmacClient::new(server.addr(), "foo");
+
+    let signature = recover_signature(&client, 3, 5).unwrap();
+    assert!(client.check(&signature).unwrap());
+}
+
+// The full challenge: every byte of a 20-byte HMAC-SHA1 at 5ms per matching byte. Takes the
+// better part of an hour even in release builds, so it only runs when asked for.
+#[test]
+#[ignore]
+fn hmac_full_signature_timing_leak_32() {
+    let server = HmacServer::start(Duration::from_millis(5)).unwrap();
+    let client = HmacClient::new(server.addr(), "foo");
+
+    let signature = recover_signature(&client, 20, 3).unwrap();
+    assert!(client.check(&signature).unwrap());
+}
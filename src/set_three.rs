@@ -0,0 +1,222 @@
+use crate::attacks::fixed_nonce_ctr::FixedNonceCtrBreaker;
+use crate::attacks::mt19937_clone::clone_from_outputs;
+use crate::attacks::mt19937_clone::clone_from_partial_outputs;
+use crate::attacks::mt19937_clone::PartialOutput;
+use crate::attacks::mt19937_seed::crack_recent_seed;
+use crate::attacks::mt19937_stream::encrypt_with_random_prefix;
+use crate::attacks::mt19937_stream::is_time_seeded_token;
+use crate::attacks::mt19937_stream::recover_mt19937_stream_seed;
+use crate::attacks::mt19937_stream::reset_token;
+use crate::attacks::padding_oracle::padding_oracle_attack;
+use crate::attacks::padding_oracle::PaddingOracleServer;
+use crate::cipher::mt19937_stream_cipher;
+use crate::cipher::Aes;
+use crate::clock::Clock;
+use crate::clock::SimulatedClock;
+use crate::modes::decrypt_aes_ecb_128;
+use crate::modes::CipherMode;
+use crate::modes::Ctr;
+use crate::rng::mt19937::N;
+use crate::rng::Mt19937;
+use rand::rngs::StdRng;
+use rand::Rng;
+use rand::SeedableRng;
+use std::fs;
+use std::time::Duration;
+
+#[test]
+fn cbc_padding_oracle_17() {
+    let server = PaddingOracleServer::new();
+
+    for line in fs::read_to_string("challenge_data/set_three/17.txt")
+        .unwrap()
+        .lines()
+    {
+        let plaintext = base64::decode(line).unwrap();
+        let (ciphertext, iv) = server.encrypt(&plaintext).unwrap();
+
+        assert_eq!(
+            padding_oracle_attack(&server, &ciphertext, &iv).unwrap(),
+            plaintext
+        );
+    }
+}
+
+#[test]
+fn ctr_mode_18() {
+    let ciphertext =
+        base64::decode("L77na/nrFsKvynd6HzOoG7GHTLXsTVu9qvY/2syLXzhPweyyMTJULu/6/kXX0KSvoOLSFQ==")
+            .unwrap();
+    let ctr = Ctr::new(Aes::new(b"YELLOW SUBMARINE").unwrap(), 0).unwrap();
+
+    assert_eq!(
+        String::from_utf8(ctr.decrypt(&ciphertext).unwrap()).unwrap(),
+        "Yo, VIP Let's kick it Ice, Ice, baby Ice, Ice, baby "
+    );
+}
+
+fn encrypt_with_fixed_nonce(plaintexts: &[Vec<u8>]) -> Vec<Vec<u8>> {
+    let ctr = Ctr::new(Aes::new(&rand::random::<[u8; 16]>()).unwrap(), 0).unwrap();
+
+    plaintexts
+        .iter()
+        .map(|plaintext| ctr.encrypt(plaintext).unwrap())
+        .collect()
+}
+
+fn correct_fraction(breaker: &FixedNonceCtrBreaker, plaintexts: &[Vec<u8>]) -> f64 {
+    let mut correct = 0;
+    let mut total = 0;
+
+    for (recovered, expected) in breaker.plaintexts().iter().zip(plaintexts) {
+        correct += recovered
+            .iter()
+            .zip(expected)
+            .filter(|(a, b)| a == b)
+            .count();
+        total += recovered.len();
+    }
+
+    correct as f64 / total as f64
+}
+
+#[test]
+fn fixed_nonce_ctr_substitutions_19() {
+    let plaintexts = fs::read_to_string("challenge_data/set_three/19.txt")
+        .unwrap()
+        .lines()
+        .map(|line| base64::decode(line).unwrap())
+        .collect::<Vec<_>>();
+
+    let mut breaker = FixedNonceCtrBreaker::new(encrypt_with_fixed_nonce(&plaintexts));
+    breaker.solve_statistically().unwrap();
+    assert_eq!(breaker.keystream().len(), breaker.common_len());
+    assert!(correct_fraction(&breaker, &plaintexts) > 0.8);
+
+    let (longest, _) = plaintexts
+        .iter()
+        .enumerate()
+        .max_by_key(|(_, plaintext)| plaintext.len())
+        .unwrap();
+    breaker.pin(longest, 0, &plaintexts[longest]).unwrap();
+
+    assert_eq!(breaker.plaintexts(), plaintexts);
+}
+
+#[test]
+fn fixed_nonce_ctr_statistics_20() {
+    let mut encrypted = vec![];
+
+    for line in fs::read_to_string("challenge_data/set_one/7.txt")
+        .unwrap()
+        .lines()
+    {
+        encrypted.append(&mut base64::decode(line).unwrap())
+    }
+
+    let lyrics = decrypt_aes_ecb_128(b"YELLOW SUBMARINE", &encrypted).unwrap();
+    let plaintexts = lyrics
+        .split(|byte| *byte == b'\n')
+        .filter(|line| line.len() >= 24)
+        .map(|line| line.to_vec())
+        .collect::<Vec<_>>();
+
+    let mut breaker = FixedNonceCtrBreaker::new(encrypt_with_fixed_nonce(&plaintexts));
+    breaker.solve_statistically().unwrap();
+
+    assert!(correct_fraction(&breaker, &plaintexts) > 0.9);
+}
+
+#[test]
+fn mt19937_21() {
+    let mut mt = Mt19937::new(1131464071);
+    let outputs = (0..3).map(|_| mt.next_u32()).collect::<Vec<_>>();
+
+    assert_eq!(outputs, [3_521_569_528, 1_101_990_581, 1_076_301_704]);
+}
+
+#[test]
+fn crack_mt19937_seed_22() {
+    let clock = SimulatedClock::at_unix_timestamp(1_600_000_000);
+    let mut rng = rand::thread_rng();
+
+    clock.sleep(Duration::from_secs(rng.gen_range(40..=1000)));
+    let seed = clock.unix_timestamp() as u32;
+    let first_output = Mt19937::new(seed).next_u32();
+    clock.sleep(Duration::from_secs(rng.gen_range(40..=1000)));
+
+    assert_eq!(crack_recent_seed(first_output, &clock, 2000), Some(seed));
+}
+
+#[test]
+fn clone_mt19937_23() {
+    let mut mt = Mt19937::new(rand::random());
+    let mut outputs = [0u32; N];
+    for output in outputs.iter_mut() {
+        *output = mt.next_u32();
+    }
+
+    let mut clone = clone_from_outputs(&outputs);
+    for _ in 0..2000 {
+        assert_eq!(clone.next_u32(), mt.next_u32());
+    }
+}
+
+#[test]
+fn clone_mt19937_with_gaps_23() {
+    let mut mt = Mt19937::new(rand::random());
+    // Whether the system is solvable depends only on which bits are observed, not on their
+    // values, so the gaps come from a fixed seed to keep the test deterministic.
+    let mut rng = StdRng::seed_from_u64(23);
+
+    // Three generations of output, with a tenth of them missing and a quarter only partly known.
+    let mut observations = vec![];
+    for index in 0..3 * N {
+        let value = mt.next_u32();
+        if rng.gen_ratio(1, 10) {
+            continue;
+        }
+
+        let mask = if rng.gen_ratio(1, 4) {
+            rng.gen::<u32>() | 0x0000_ffff
+        } else {
+            u32::MAX
+        };
+        observations.push(PartialOutput { index, value, mask });
+    }
+
+    let mut clone = clone_from_partial_outputs(&observations).unwrap();
+    for _ in 0..3 * N {
+        clone.next_u32();
+    }
+    for _ in 0..2000 {
+        assert_eq!(clone.next_u32(), mt.next_u32());
+    }
+}
+
+#[test]
+fn mt19937_stream_cipher_24() {
+    let mut data = b"Lorem ipsum dolor sit amet".to_vec();
+    mt19937_stream_cipher(0xbeef, &mut data);
+    assert_ne!(&data[..], b"Lorem ipsum dolor sit amet");
+    mt19937_stream_cipher(0xbeef, &mut data);
+    assert_eq!(&data[..], b"Lorem ipsum dolor sit amet");
+
+    let seed = rand::random::<u16>();
+    let ciphertext = encrypt_with_random_prefix(seed, &[b'A'; 14]);
+    assert_eq!(
+        recover_mt19937_stream_seed(&ciphertext, &[b'A'; 14]),
+        Some(seed)
+    );
+
+    let clock = SimulatedClock::at_unix_timestamp(1_600_000_000);
+    let token = reset_token(&clock);
+    clock.sleep(Duration::from_secs(90));
+
+    assert!(is_time_seeded_token(&token, &clock, 3600));
+    assert!(!is_time_seeded_token(
+        &rand::random::<[u8; 16]>(),
+        &clock,
+        3600
+    ));
+}
@@ -0,0 +1,47 @@
+use crate::Error;
+use crate::Result;
+
+pub fn fixed_xor(x: &mut [u8], y: &[u8]) -> Result<()> {
+    if x.len() != y.len() {
+        return Err(Error::LengthMismatch {
+            expected: x.len(),
+            actual: y.len(),
+        });
+    }
+
+    for (a, b) in x.iter_mut().zip(y) {
+        *a ^= b
+    }
+
+    Ok(())
+}
+
+pub fn repeating_key_xor(key: &[u8], data: &mut [u8]) -> Result<()> {
+    let key_len = key.len();
+    if key_len == 0 {
+        return Err(Error::InvalidKeySize(key_len));
+    }
+
+    for (i, el) in data.iter_mut().enumerate() {
+        *el ^= key[i % key_len];
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Error;
+
+    #[test]
+    fn fixed_xor_length_mismatch() {
+        let mut x = vec![0u8; 4];
+
+        match super::fixed_xor(&mut x, &[0u8; 3]) {
+            Err(Error::LengthMismatch { expected, actual }) => {
+                assert_eq!((expected, actual), (4, 3))
+            }
+            other => panic!("unexpected result: {:?}", other),
+        }
+    }
+}